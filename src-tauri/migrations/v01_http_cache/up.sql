@@ -0,0 +1,3 @@
+-- cache validators of the last feed response, for conditional requests
+ALTER TABLE channels ADD COLUMN etag VARCHAR NOT NULL DEFAULT '';
+ALTER TABLE channels ADD COLUMN last_modified VARCHAR NOT NULL DEFAULT '';
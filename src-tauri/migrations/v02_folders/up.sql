@@ -0,0 +1,12 @@
+CREATE TABLE folders (
+  id INTEGER NOT NULL PRIMARY KEY,
+  name VARCHAR NOT NULL UNIQUE
+);
+
+-- a channel is in one folder at most
+CREATE TABLE channel_folders (
+  channel_link VARCHAR NOT NULL PRIMARY KEY,
+  folder_id INTEGER NOT NULL
+);
+
+CREATE INDEX channel_folders_folder_id ON channel_folders (folder_id);
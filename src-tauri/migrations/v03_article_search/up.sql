@@ -0,0 +1,31 @@
+-- full-text index over articles, kept in sync by triggers
+CREATE VIRTUAL TABLE articles_fts USING fts5(
+  title,
+  description,
+  content,
+  author,
+  content = 'articles',
+  content_rowid = 'id',
+  tokenize = 'unicode61 remove_diacritics 2'
+);
+
+-- index the existing articles
+INSERT INTO articles_fts(articles_fts) VALUES ('rebuild');
+
+CREATE TRIGGER articles_fts_insert AFTER INSERT ON articles BEGIN
+  INSERT INTO articles_fts(rowid, title, description, content, author)
+  VALUES (new.id, new.title, new.description, new.content, new.author);
+END;
+
+CREATE TRIGGER articles_fts_delete AFTER DELETE ON articles BEGIN
+  INSERT INTO articles_fts(articles_fts, rowid, title, description, content, author)
+  VALUES ('delete', old.id, old.title, old.description, old.content, old.author);
+END;
+
+CREATE TRIGGER articles_fts_update
+AFTER UPDATE OF title, description, content, author ON articles BEGIN
+  INSERT INTO articles_fts(articles_fts, rowid, title, description, content, author)
+  VALUES ('delete', old.id, old.title, old.description, old.content, old.author);
+  INSERT INTO articles_fts(rowid, title, description, content, author)
+  VALUES (new.id, new.title, new.description, new.content, new.author);
+END;
@@ -0,0 +1,3 @@
+-- This file should undo anything in `up.sql`
+DROP INDEX articles_feed_link_published;
+DROP INDEX articles_published;
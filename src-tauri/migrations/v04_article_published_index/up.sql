@@ -0,0 +1,3 @@
+-- get articles sorted by published, per channel or all
+CREATE INDEX articles_published ON articles (published, id);
+CREATE INDEX articles_feed_link_published ON articles (feed_link, published, id);
@@ -0,0 +1,2 @@
+-- This file should undo anything in `up.sql`
+-- the original date strings are gone, timestamps are kept
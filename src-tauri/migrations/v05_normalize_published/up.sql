@@ -0,0 +1,15 @@
+-- published as utc timestamp, like `2024-03-06 00:00:00+00:00`
+--
+-- ISO 8601 dates are converted here, the others (RFC 2822 and the like)
+-- are parsed by the app on start, see `db::normalize_published`
+UPDATE articles
+SET published = strftime('%Y-%m-%d %H:%M:%S+00:00', published)
+WHERE published GLOB '[0-9][0-9][0-9][0-9]-[0-9][0-9]-[0-9][0-9]*'
+  AND strftime('%Y-%m-%d %H:%M:%S+00:00', published) IS NOT NULL;
+
+UPDATE channels
+SET published = strftime('%Y-%m-%d %H:%M:%S+00:00', published)
+WHERE published GLOB '[0-9][0-9][0-9][0-9]-[0-9][0-9]-[0-9][0-9]*'
+  AND strftime('%Y-%m-%d %H:%M:%S+00:00', published) IS NOT NULL;
+
+UPDATE channels SET published = NULL WHERE trim(published) = '';
@@ -0,0 +1,8 @@
+-- retention of a channel, overriding the global one
+-- keep_num: keep the last N articles, keep_days: keep those of last N days
+CREATE TABLE channel_retention (
+  channel_link VARCHAR NOT NULL PRIMARY KEY,
+  keep_num INTEGER,
+  keep_days INTEGER,
+  keep_unread BOOLEAN NOT NULL DEFAULT 0
+);
@@ -0,0 +1,2 @@
+-- 0: as fetched 1: revised by the feed since
+ALTER TABLE articles ADD COLUMN update_status INTEGER NOT NULL DEFAULT 0;
@@ -0,0 +1,48 @@
+-- This file should undo anything in `up.sql`
+-- articles sharing an url are dropped but the first
+CREATE TABLE articles_old (
+  id INTEGER NOT NULL PRIMARY KEY,
+  title VARCHAR NOT NULL,
+  url VARCHAR NOT NULL UNIQUE,
+  feed_link VARCHAR NOT NULL,
+  audio_url VARCHAR NOT NULL DEFAULT '',
+  description VARCHAR NOT NULL,
+  published DATETIME,
+  content VARCHAR,
+  author VARCHAR,
+  image VARCHAR,
+  read_status INTEGER NOT NULL DEFAULT 0,
+  star_status INTEGER NOT NULL DEFAULT 0,
+  update_status INTEGER NOT NULL DEFAULT 0
+);
+
+INSERT OR IGNORE INTO articles_old
+SELECT id, title, url, feed_link, audio_url, description, published,
+  content, author, image, read_status, star_status, update_status
+FROM articles ORDER BY id;
+
+DROP TABLE articles;
+ALTER TABLE articles_old RENAME TO articles;
+
+CREATE INDEX articles_published ON articles (published, id);
+CREATE INDEX articles_feed_link_published ON articles (feed_link, published, id);
+
+CREATE TRIGGER articles_fts_insert AFTER INSERT ON articles BEGIN
+  INSERT INTO articles_fts(rowid, title, description, content, author)
+  VALUES (new.id, new.title, new.description, new.content, new.author);
+END;
+
+CREATE TRIGGER articles_fts_delete AFTER DELETE ON articles BEGIN
+  INSERT INTO articles_fts(articles_fts, rowid, title, description, content, author)
+  VALUES ('delete', old.id, old.title, old.description, old.content, old.author);
+END;
+
+CREATE TRIGGER articles_fts_update
+AFTER UPDATE OF title, description, content, author ON articles BEGIN
+  INSERT INTO articles_fts(articles_fts, rowid, title, description, content, author)
+  VALUES ('delete', old.id, old.title, old.description, old.content, old.author);
+  INSERT INTO articles_fts(rowid, title, description, content, author)
+  VALUES (new.id, new.title, new.description, new.content, new.author);
+END;
+
+INSERT INTO articles_fts(articles_fts) VALUES ('rebuild');
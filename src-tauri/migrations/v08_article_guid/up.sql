@@ -0,0 +1,55 @@
+-- an article is identified by its guid in the feed, not by the url:
+-- items without link or two feeds linking the same url no longer collide
+--
+-- sqlite can not drop a UNIQUE constraint, rebuild the table
+CREATE TABLE articles_new (
+  id INTEGER NOT NULL PRIMARY KEY,
+  title VARCHAR NOT NULL,
+  url VARCHAR NOT NULL,
+  feed_link VARCHAR NOT NULL,
+  audio_url VARCHAR NOT NULL DEFAULT '',
+  description VARCHAR NOT NULL,
+  published DATETIME,
+  content VARCHAR NOT NULL DEFAULT '',
+  author VARCHAR NOT NULL DEFAULT '',
+  image VARCHAR NOT NULL DEFAULT '',
+  read_status INTEGER NOT NULL DEFAULT 0, -- 0: unread 1: read
+  star_status INTEGER NOT NULL DEFAULT 0, -- 0: unstar 1: star-ed
+  update_status INTEGER NOT NULL DEFAULT 0, -- 0: as fetched 1: revised by the feed
+  guid VARCHAR NOT NULL, -- RSS guid, Atom or JSON Feed id, or the url
+  UNIQUE (feed_link, guid)
+);
+
+-- keep the ids, they are the rowids of articles_fts
+INSERT INTO articles_new
+SELECT id, title, url, feed_link, audio_url, description, published,
+  coalesce(content, ''), coalesce(author, ''), coalesce(image, ''),
+  read_status, star_status, update_status, url
+FROM articles;
+
+DROP TABLE articles;
+ALTER TABLE articles_new RENAME TO articles;
+
+CREATE INDEX articles_url ON articles (url);
+CREATE INDEX articles_published ON articles (published, id);
+CREATE INDEX articles_feed_link_published ON articles (feed_link, published, id);
+
+CREATE TRIGGER articles_fts_insert AFTER INSERT ON articles BEGIN
+  INSERT INTO articles_fts(rowid, title, description, content, author)
+  VALUES (new.id, new.title, new.description, new.content, new.author);
+END;
+
+CREATE TRIGGER articles_fts_delete AFTER DELETE ON articles BEGIN
+  INSERT INTO articles_fts(articles_fts, rowid, title, description, content, author)
+  VALUES ('delete', old.id, old.title, old.description, old.content, old.author);
+END;
+
+CREATE TRIGGER articles_fts_update
+AFTER UPDATE OF title, description, content, author ON articles BEGIN
+  INSERT INTO articles_fts(articles_fts, rowid, title, description, content, author)
+  VALUES ('delete', old.id, old.title, old.description, old.content, old.author);
+  INSERT INTO articles_fts(rowid, title, description, content, author)
+  VALUES (new.id, new.title, new.description, new.content, new.author);
+END;
+
+INSERT INTO articles_fts(articles_fts) VALUES ('rebuild');
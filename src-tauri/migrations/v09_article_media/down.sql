@@ -0,0 +1,3 @@
+-- This file should undo anything in `up.sql`
+ALTER TABLE articles DROP COLUMN video_url;
+ALTER TABLE articles DROP COLUMN categories;
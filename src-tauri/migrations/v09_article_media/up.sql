@@ -0,0 +1,3 @@
+-- categories joined by `, `, and the video enclosure
+ALTER TABLE articles ADD COLUMN categories VARCHAR NOT NULL DEFAULT '';
+ALTER TABLE articles ADD COLUMN video_url VARCHAR NOT NULL DEFAULT '';
@@ -0,0 +1,9 @@
+-- This file should undo anything in `up.sql`
+ALTER TABLE channels DROP COLUMN image;
+
+ALTER TABLE articles DROP COLUMN transcript_url;
+ALTER TABLE articles DROP COLUMN chapters_url;
+ALTER TABLE articles DROP COLUMN explicit;
+ALTER TABLE articles DROP COLUMN episode;
+ALTER TABLE articles DROP COLUMN season;
+ALTER TABLE articles DROP COLUMN duration;
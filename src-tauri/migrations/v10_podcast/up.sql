@@ -0,0 +1,10 @@
+-- podcast episodes, per the iTunes and Podcasting 2.0 namespaces
+ALTER TABLE articles ADD COLUMN duration INTEGER; -- seconds
+ALTER TABLE articles ADD COLUMN season INTEGER;
+ALTER TABLE articles ADD COLUMN episode INTEGER;
+ALTER TABLE articles ADD COLUMN explicit BOOLEAN NOT NULL DEFAULT 0;
+ALTER TABLE articles ADD COLUMN chapters_url VARCHAR NOT NULL DEFAULT '';
+ALTER TABLE articles ADD COLUMN transcript_url VARCHAR NOT NULL DEFAULT '';
+
+-- podcast artwork, or the channel image
+ALTER TABLE channels ADD COLUMN image VARCHAR NOT NULL DEFAULT '';
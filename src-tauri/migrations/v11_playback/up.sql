@@ -0,0 +1,18 @@
+-- playback of podcast episodes, per audio url
+CREATE TABLE playback (
+  audio_url VARCHAR NOT NULL PRIMARY KEY,
+  position DOUBLE NOT NULL DEFAULT 0, -- seconds
+  duration DOUBLE, -- seconds
+  completed BOOLEAN NOT NULL DEFAULT 0,
+  updated_at DATETIME NOT NULL
+);
+
+-- episodes to play, in queue_order
+CREATE TABLE play_queue (
+  audio_url VARCHAR NOT NULL PRIMARY KEY,
+  title VARCHAR NOT NULL,
+  article_url VARCHAR NOT NULL DEFAULT '',
+  feed_link VARCHAR NOT NULL DEFAULT '',
+  published DATETIME,
+  queue_order INTEGER NOT NULL
+);
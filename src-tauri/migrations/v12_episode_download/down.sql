@@ -0,0 +1,2 @@
+-- This file should undo anything in `up.sql`
+ALTER TABLE articles DROP COLUMN local_audio_path;
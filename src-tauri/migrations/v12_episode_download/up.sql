@@ -0,0 +1,2 @@
+-- the downloaded audio, played instead of audio_url if any
+ALTER TABLE articles ADD COLUMN local_audio_path VARCHAR NOT NULL DEFAULT '';
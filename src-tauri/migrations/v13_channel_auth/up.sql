@@ -0,0 +1,8 @@
+-- credentials of a channel, sent on fetching the feed
+-- kind: basic, with username and secret as password; bearer, secret as token
+CREATE TABLE channel_auth (
+  channel_link VARCHAR NOT NULL PRIMARY KEY,
+  kind VARCHAR NOT NULL,
+  username VARCHAR NOT NULL DEFAULT '',
+  secret VARCHAR NOT NULL DEFAULT ''
+);
@@ -0,0 +1,5 @@
+-- This file should undo anything in `up.sql`
+ALTER TABLE channels DROP COLUMN last_error;
+ALTER TABLE channels DROP COLUMN failure_count;
+ALTER TABLE channels DROP COLUMN last_success;
+ALTER TABLE channels DROP COLUMN last_fetched;
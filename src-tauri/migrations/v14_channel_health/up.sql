@@ -0,0 +1,6 @@
+-- health of fetching a channel
+-- failure_count: consecutive failed fetches, reset on success
+ALTER TABLE channels ADD COLUMN last_fetched DATETIME;
+ALTER TABLE channels ADD COLUMN last_success DATETIME;
+ALTER TABLE channels ADD COLUMN failure_count INTEGER NOT NULL DEFAULT 0;
+ALTER TABLE channels ADD COLUMN last_error VARCHAR NOT NULL DEFAULT '';
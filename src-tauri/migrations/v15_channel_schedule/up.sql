@@ -0,0 +1,5 @@
+-- refresh schedule of a channel, in minutes
+-- ttl: hint of the feed, per <ttl> or sy:updatePeriod
+-- refresh_interval: set by user, the global one on NULL
+ALTER TABLE channels ADD COLUMN ttl INTEGER;
+ALTER TABLE channels ADD COLUMN refresh_interval INTEGER;
@@ -0,0 +1,2 @@
+-- notify the new articles of a channel on refresh
+ALTER TABLE channels ADD COLUMN notify BOOLEAN NOT NULL DEFAULT 0;
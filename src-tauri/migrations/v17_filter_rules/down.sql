@@ -0,0 +1,4 @@
+-- This file should undo anything in `up.sql`
+ALTER TABLE articles DROP COLUMN hide_status;
+
+DROP TABLE filter_rules;
@@ -0,0 +1,14 @@
+-- rules to filter articles on ingest, or re-applied to existing ones
+-- channel_link: NULL for all channels
+-- kind: keyword, regex, author or category
+-- action: read, star, hide or delete
+CREATE TABLE filter_rules (
+  id INTEGER NOT NULL PRIMARY KEY,
+  channel_link VARCHAR,
+  kind VARCHAR NOT NULL,
+  pattern VARCHAR NOT NULL,
+  action VARCHAR NOT NULL
+);
+
+-- hidden by a filter rule
+ALTER TABLE articles ADD COLUMN hide_status INTEGER NOT NULL DEFAULT 0;
@@ -0,0 +1,4 @@
+-- This file should undo anything in `up.sql`
+ALTER TABLE channels DROP COLUMN full_article;
+
+ALTER TABLE articles DROP COLUMN full_status;
@@ -0,0 +1,5 @@
+-- the content extracted from the article page, 1: extracted
+ALTER TABLE articles ADD COLUMN full_status INTEGER NOT NULL DEFAULT 0;
+
+-- extract the full content of the new articles of a channel on refresh
+ALTER TABLE channels ADD COLUMN full_article BOOLEAN NOT NULL DEFAULT 0;
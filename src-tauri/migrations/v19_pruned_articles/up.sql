@@ -0,0 +1,7 @@
+-- the articles pruned per retention, not to be inserted again as new
+-- while they are still in the feed
+CREATE TABLE pruned_articles (
+  feed_link VARCHAR NOT NULL,
+  guid VARCHAR NOT NULL,
+  PRIMARY KEY (feed_link, guid)
+);
@@ -1,10 +1,17 @@
-use crate::models::{Article, Channel, NewArticle, NewChannel};
+use crate::feed::{date, filter, secret};
+use crate::models::{
+  Article, Channel, ChannelAuth, FilterRule, Folder, NewArticle, NewChannel,
+  NewFilterRule, Playback, QueueItem, RetentionPolicy,
+};
 use crate::schema;
 use crate::storage::{create_mdsilo_dir, do_log};
 use chrono::offset::Local;
+use chrono::{DateTime, Utc};
+use diesel::connection::SimpleConnection;
 use diesel::prelude::*;
 use diesel::sqlite::SqliteConnection;
 use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
 
 pub fn establish_connection() -> SqliteConnection {
   let data_path = create_mdsilo_dir().expect("Error on creating data dir");
@@ -16,7 +23,7 @@ pub fn establish_connection() -> SqliteConnection {
     .clone()
     .expect("Error on converting db path to url");
 
-  SqliteConnection::establish(&database_url)
+  let mut connection = SqliteConnection::establish(&database_url)
     .map_err(|e| {
       do_log(
         "Error".to_string(),
@@ -24,7 +31,77 @@ pub fn establish_connection() -> SqliteConnection {
         format!("{}", Local::now().format("%m/%d/%Y %H:%M:%S")),
       )
     })
-    .expect("Error on connecting to database")
+    .expect("Error on connecting to database");
+
+  // channels can be refreshed concurrently, wait on a locked db;
+  // the writes take the lock upfront in an immediate transaction,
+  // a deferred one fails busy at once on upgrading its read lock
+  connection
+    .batch_execute("PRAGMA busy_timeout = 5000;")
+    .unwrap_or(());
+
+  connection
+}
+
+#[derive(Debug, QueryableByName)]
+struct RawPublished {
+  #[diesel(sql_type = diesel::sql_types::Integer)]
+  id: i32,
+  #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+  published: Option<String>,
+}
+
+// dates saved verbatim by older versions into utc timestamps,
+// an article without a valid date gets the current time
+pub fn normalize_published() -> usize {
+  const SQL_RAW_ARTICLES: &str = "
+    SELECT id, published FROM articles
+    WHERE published IS NULL OR published NOT GLOB ?
+    ";
+  const SQL_RAW_CHANNELS: &str = "
+    SELECT id, published FROM channels WHERE published NOT GLOB ?
+    ";
+  // as diesel saves a DateTime<Utc>
+  const TIMESTAMP_GLOB: &str =
+    "[0-9][0-9][0-9][0-9]-[0-9][0-9]-[0-9][0-9] [0-9][0-9]:[0-9][0-9]:[0-9][0-9]+00:00";
+  let load_raw = |connection: &mut SqliteConnection, sql: &str| {
+    diesel::sql_query(sql)
+      .bind::<diesel::sql_types::Text, _>(TIMESTAMP_GLOB)
+      .load::<RawPublished>(connection)
+  };
+
+  let mut connection = establish_connection();
+  let now = date::now();
+  let result = connection.immediate_transaction(|conn| {
+    let mut num = 0;
+    for raw in load_raw(conn, SQL_RAW_ARTICLES)? {
+      let published = raw
+        .published
+        .as_deref()
+        .and_then(date::parse_date)
+        .unwrap_or(now);
+      num += diesel::update(schema::articles::dsl::articles.find(raw.id))
+        .set(schema::articles::published.eq(published))
+        .execute(conn)?;
+    }
+    for raw in load_raw(conn, SQL_RAW_CHANNELS)? {
+      let published = raw.published.as_deref().and_then(date::parse_date);
+      num += diesel::update(schema::channels::dsl::channels.find(raw.id))
+        .set(schema::channels::published.eq(published))
+        .execute(conn)?;
+    }
+    diesel::QueryResult::Ok(num)
+  });
+
+  result
+    .map_err(|e| {
+      do_log(
+        "Error".to_string(),
+        format!("db Error on [normalize_published]: {:?}", e),
+        format!("{}", Local::now().format("%m/%d/%Y %H:%M:%S")),
+      )
+    })
+    .unwrap_or(0)
 }
 
 pub fn get_channels() -> Vec<Channel> {
@@ -62,9 +139,7 @@ pub fn add_channel(channel: NewChannel, articles: Vec<NewArticle>) -> usize {
 
   // TODO: check if add channel failed
   // insert articles
-  diesel::insert_or_ignore_into(schema::articles::dsl::articles)
-    .values(articles)
-    .execute(&mut connection)
+  upsert_articles(&mut connection, articles)
     .map_err(|e| {
       do_log(
         "Error".to_string(),
@@ -72,7 +147,7 @@ pub fn add_channel(channel: NewChannel, articles: Vec<NewArticle>) -> usize {
         format!("{}", Local::now().format("%m/%d/%Y %H:%M:%S")),
       )
     })
-    .unwrap_or(0);
+    .unwrap_or_default();
 
   return result;
 }
@@ -124,6 +199,76 @@ pub fn delete_channel(link: String) -> usize {
     })
     .unwrap_or(0);
 
+    diesel::delete(
+      schema::channel_folders::dsl::channel_folders
+        .filter(schema::channel_folders::channel_link.eq(&link)),
+    )
+    .execute(&mut connection)
+    .map_err(|e| {
+      do_log(
+        "Error".to_string(),
+        format!("db Error on [delete_channel: del folder link]: {:?}", e),
+        format!("{}", Local::now().format("%m/%d/%Y %H:%M:%S")),
+      )
+    })
+    .unwrap_or(0);
+
+    diesel::delete(
+      schema::channel_retention::dsl::channel_retention
+        .filter(schema::channel_retention::channel_link.eq(&link)),
+    )
+    .execute(&mut connection)
+    .map_err(|e| {
+      do_log(
+        "Error".to_string(),
+        format!("db Error on [delete_channel: del retention]: {:?}", e),
+        format!("{}", Local::now().format("%m/%d/%Y %H:%M:%S")),
+      )
+    })
+    .unwrap_or(0);
+
+    diesel::delete(
+      schema::channel_auth::dsl::channel_auth
+        .filter(schema::channel_auth::channel_link.eq(&link)),
+    )
+    .execute(&mut connection)
+    .map_err(|e| {
+      do_log(
+        "Error".to_string(),
+        format!("db Error on [delete_channel: del auth]: {:?}", e),
+        format!("{}", Local::now().format("%m/%d/%Y %H:%M:%S")),
+      )
+    })
+    .unwrap_or(0);
+
+    diesel::delete(
+      schema::filter_rules::dsl::filter_rules
+        .filter(schema::filter_rules::channel_link.eq(&link)),
+    )
+    .execute(&mut connection)
+    .map_err(|e| {
+      do_log(
+        "Error".to_string(),
+        format!("db Error on [delete_channel: del filter rules]: {:?}", e),
+        format!("{}", Local::now().format("%m/%d/%Y %H:%M:%S")),
+      )
+    })
+    .unwrap_or(0);
+
+    diesel::delete(
+      schema::pruned_articles::dsl::pruned_articles
+        .filter(schema::pruned_articles::feed_link.eq(&link)),
+    )
+    .execute(&mut connection)
+    .map_err(|e| {
+      do_log(
+        "Error".to_string(),
+        format!("db Error on [delete_channel: del pruned articles]: {:?}", e),
+        format!("{}", Local::now().format("%m/%d/%Y %H:%M:%S")),
+      )
+    })
+    .unwrap_or(0);
+
     return result;
   } else {
     return 0;
@@ -151,6 +296,171 @@ pub fn get_channel_by_link(link: String) -> Option<Channel> {
   }
 }
 
+pub fn update_channel_http_cache(
+  link: String,
+  etag: String,
+  last_modified: String,
+) -> usize {
+  let mut connection = establish_connection();
+  diesel::update(
+    schema::channels::dsl::channels.filter(schema::channels::link.eq(&link)),
+  )
+  .set((
+    schema::channels::etag.eq(etag),
+    schema::channels::last_modified.eq(last_modified),
+  ))
+  .execute(&mut connection)
+  .map_err(|e| {
+    do_log(
+      "Error".to_string(),
+      format!("db Error on [update_channel_http_cache, {}]: {:?}", link, e),
+      format!("{}", Local::now().format("%m/%d/%Y %H:%M:%S")),
+    )
+  })
+  .unwrap_or(0)
+}
+
+// the refresh hint and the artwork of the feed may change;
+// an image missing from the feed keeps the stored one
+pub fn update_channel_meta(
+  link: String,
+  ttl: Option<i32>,
+  image: String,
+) -> usize {
+  use schema::channels::dsl;
+
+  let mut connection = establish_connection();
+  connection
+    .immediate_transaction(|conn| {
+      let channel = || dsl::channels.filter(dsl::link.eq(&link));
+      let mut updated =
+        diesel::update(channel()).set(dsl::ttl.eq(ttl)).execute(conn)?;
+      if !image.is_empty() {
+        updated = diesel::update(channel())
+          .set(dsl::image.eq(&image))
+          .execute(conn)?;
+      }
+      diesel::QueryResult::Ok(updated)
+    })
+    .map_err(|e| {
+      do_log(
+        "Error".to_string(),
+        format!("db Error on [update_channel_meta, {}]: {:?}", link, e),
+        format!("{}", Local::now().format("%m/%d/%Y %H:%M:%S")),
+      )
+    })
+    .unwrap_or(0)
+}
+
+// set the refresh interval of a channel in minutes,
+// or follow the global one on None
+pub fn set_channel_refresh_interval(link: String, minutes: Option<i32>) -> usize {
+  let mut connection = establish_connection();
+  diesel::update(
+    schema::channels::dsl::channels.filter(schema::channels::link.eq(&link)),
+  )
+  .set(schema::channels::refresh_interval.eq(minutes))
+  .execute(&mut connection)
+  .map_err(|e| {
+    do_log(
+      "Error".to_string(),
+      format!("db Error on [set_channel_refresh_interval, {}]: {:?}", link, e),
+      format!("{}", Local::now().format("%m/%d/%Y %H:%M:%S")),
+    )
+  })
+  .unwrap_or(0)
+}
+
+pub fn set_channel_notify(link: String, notify: bool) -> usize {
+  let mut connection = establish_connection();
+  diesel::update(
+    schema::channels::dsl::channels.filter(schema::channels::link.eq(&link)),
+  )
+  .set(schema::channels::notify.eq(notify))
+  .execute(&mut connection)
+  .map_err(|e| {
+    do_log(
+      "Error".to_string(),
+      format!("db Error on [set_channel_notify, {}]: {:?}", link, e),
+      format!("{}", Local::now().format("%m/%d/%Y %H:%M:%S")),
+    )
+  })
+  .unwrap_or(0)
+}
+
+pub fn set_channel_full_article(link: String, full_article: bool) -> usize {
+  let mut connection = establish_connection();
+  diesel::update(
+    schema::channels::dsl::channels.filter(schema::channels::link.eq(&link)),
+  )
+  .set(schema::channels::full_article.eq(full_article))
+  .execute(&mut connection)
+  .map_err(|e| {
+    do_log(
+      "Error".to_string(),
+      format!("db Error on [set_channel_full_article, {}]: {:?}", link, e),
+      format!("{}", Local::now().format("%m/%d/%Y %H:%M:%S")),
+    )
+  })
+  .unwrap_or(0)
+}
+
+// record a fetch of the channel, failed on an error
+pub fn record_channel_fetch(link: String, error: Option<String>) -> usize {
+  let mut connection = establish_connection();
+  let now = date::now();
+  let channel =
+    schema::channels::dsl::channels.filter(schema::channels::link.eq(&link));
+  let result = match error {
+    Some(error) => diesel::update(channel)
+      .set((
+        schema::channels::last_fetched.eq(now),
+        schema::channels::failure_count.eq(schema::channels::failure_count + 1),
+        schema::channels::last_error.eq(error),
+      ))
+      .execute(&mut connection),
+    None => diesel::update(channel)
+      .set((
+        schema::channels::last_fetched.eq(now),
+        schema::channels::last_success.eq(now),
+        schema::channels::failure_count.eq(0),
+        schema::channels::last_error.eq(""),
+      ))
+      .execute(&mut connection),
+  };
+
+  result
+    .map_err(|e| {
+      do_log(
+        "Error".to_string(),
+        format!("db Error on [record_channel_fetch, {}]: {:?}", link, e),
+        format!("{}", Local::now().format("%m/%d/%Y %H:%M:%S")),
+      )
+    })
+    .unwrap_or(0)
+}
+
+// channels failed at least the number of times in a row,
+// the most failed first
+pub fn get_broken_channels(min_failures: i32) -> Vec<Channel> {
+  let mut connection = establish_connection();
+  schema::channels::dsl::channels
+    .filter(schema::channels::failure_count.ge(min_failures.max(1)))
+    .order((
+      schema::channels::failure_count.desc(),
+      schema::channels::title.asc(),
+    ))
+    .load::<Channel>(&mut connection)
+    .map_err(|e| {
+      do_log(
+        "Error".to_string(),
+        format!("db Error on [get_broken_channels]: {:?}", e),
+        format!("{}", Local::now().format("%m/%d/%Y %H:%M:%S")),
+      )
+    })
+    .unwrap_or_else(|_| vec![])
+}
+
 #[derive(Debug, Queryable, Serialize, QueryableByName)]
 pub struct UnreadNum {
   #[diesel(sql_type = diesel::sql_types::Text)]
@@ -179,50 +489,253 @@ pub fn get_unread_num() -> Vec<UnreadNum> {
   return record;
 }
 
-pub fn add_articles(feed_link: String, articles: Vec<NewArticle>) -> usize {
+#[derive(Debug, Queryable, Serialize, QueryableByName)]
+pub struct FolderUnreadNum {
+  #[diesel(sql_type = diesel::sql_types::Integer)]
+  pub folder_id: i32,
+  #[diesel(sql_type = diesel::sql_types::Integer)]
+  pub unread_count: i32,
+}
+
+pub fn get_folder_unread_num() -> Vec<FolderUnreadNum> {
+  const SQL_QUERY_FOLDER_UNREAD_NUM: &str = "
+    SELECT cf.folder_id, count(a.id) as unread_count
+    FROM articles a INNER JOIN channel_folders cf ON a.feed_link = cf.channel_link
+    WHERE a.read_status = 0 group by cf.folder_id;
+    ";
   let mut connection = establish_connection();
-  let channel = schema::channels::dsl::channels
-    .filter(schema::channels::link.eq(&feed_link))
-    .load::<Channel>(&mut connection)
+  diesel::sql_query(SQL_QUERY_FOLDER_UNREAD_NUM)
+    .load::<FolderUnreadNum>(&mut connection)
     .map_err(|e| {
       do_log(
         "Error".to_string(),
-        format!(
-          "db Error on [add_articles: get channel, {}]: {:?}",
-          feed_link, e
-        ),
+        format!("db Error on [get_folder_unread_num]: {:?}", e),
         format!("{}", Local::now().format("%m/%d/%Y %H:%M:%S")),
       )
     })
-    .unwrap_or_else(|_| vec![]);
+    .unwrap_or_else(|_| vec![])
+}
 
-  if channel.len() == 1 {
-    let result = diesel::insert_or_ignore_into(schema::articles::dsl::articles)
-      .values(articles)
-      .execute(&mut connection)
-      .map_err(|e| {
-        do_log(
-          "Error".to_string(),
-          format!(
-            "db Error on [add_articles: to channel, {}]: {:?}",
-            feed_link, e
-          ),
-          format!("{}", Local::now().format("%m/%d/%Y %H:%M:%S")),
-        )
-      })
-      .unwrap_or(0);
+// get the new articles
+pub fn add_articles(
+  feed_link: String,
+  articles: Vec<NewArticle>,
+) -> QueryResult<Vec<AddedArticle>> {
+  let mut connection = establish_connection();
+  schema::channels::dsl::channels
+    .filter(schema::channels::link.eq(&feed_link))
+    .count()
+    .get_result::<i64>(&mut connection)
+    .and_then(|num| match num {
+      1 => upsert_articles(&mut connection, articles),
+      _ => Ok(vec![]),
+    })
+    .map_err(|e| {
+      do_log(
+        "Error".to_string(),
+        format!("db Error on [add_articles: to channel, {}]: {:?}", feed_link, e),
+        format!("{}", Local::now().format("%m/%d/%Y %H:%M:%S")),
+      );
+      e
+    })
+}
 
-    return result;
-  } else {
-    return 0;
+// a new article, to notify or to extract the full content
+#[derive(Debug, Clone, PartialEq)]
+pub struct AddedArticle {
+  pub id: i32,
+  pub title: String,
+  pub url: String,
+}
+
+// insert new articles, update the revised ones per guid,
+// keep the read and star status; get the new articles,
+// except the ones hidden or deleted per the filter rules
+fn upsert_articles(
+  connection: &mut SqliteConnection,
+  articles: Vec<NewArticle>,
+) -> QueryResult<Vec<AddedArticle>> {
+  use schema::articles::dsl;
+
+  connection.immediate_transaction(|conn| {
+    let rules =
+      filter::RuleSet::new(schema::filter_rules::dsl::filter_rules.load(conn)?);
+    // the extracted content is kept over the summary in the feed
+    let feed_links: Vec<&String> = articles.iter().map(|a| &a.feed_link).collect();
+    let extracted: HashMap<String, String> = dsl::articles
+      .filter(dsl::feed_link.eq_any(&feed_links))
+      .filter(dsl::full_status.eq(1))
+      .select((dsl::guid, dsl::content))
+      .load::<(String, String)>(conn)?
+      .into_iter()
+      .collect();
+    let pruned: HashSet<String> = schema::pruned_articles::dsl::pruned_articles
+      .filter(schema::pruned_articles::feed_link.eq_any(&feed_links))
+      .select(schema::pruned_articles::guid)
+      .load::<String>(conn)?
+      .into_iter()
+      .collect();
+    let mut added = vec![];
+    for mut article in articles {
+      // pruned per retention, or pruned as a legacy row keyed by url
+      if pruned.contains(&article.guid) || pruned.contains(&article.url) {
+        continue;
+      }
+      if let Some(content) = extracted.get(&article.guid) {
+        article.content = content.clone();
+      }
+      let actions = rules.actions(&filter::Item::from(&article));
+      if actions.delete {
+        continue;
+      }
+      if article.guid != article.url {
+        adopt_legacy_guid(conn, &article)?;
+      }
+      let inserted = diesel::insert_or_ignore_into(dsl::articles)
+        .values(&article)
+        .execute(conn)?;
+      if inserted > 0 {
+        let id = dsl::articles
+          .filter(dsl::feed_link.eq(&article.feed_link))
+          .filter(dsl::guid.eq(&article.guid))
+          .select(dsl::id)
+          .first::<i32>(conn)?;
+        if actions.any() {
+          apply_filter_actions(conn, id, &actions)?;
+        }
+        if !actions.hide {
+          added.push(AddedArticle {
+            id,
+            title: article.title,
+            url: article.url,
+          });
+        }
+        continue;
+      }
+
+      let stored = dsl::articles
+        .filter(dsl::feed_link.eq(&article.feed_link))
+        .filter(dsl::guid.eq(&article.guid));
+      // revised by the feed: the text changed
+      diesel::update(
+        stored.filter(
+          dsl::title
+            .is_not(&article.title)
+            .or(dsl::description.is_not(&article.description))
+            .or(dsl::content.is_not(&article.content)),
+        ),
+      )
+      .set((
+        dsl::title.eq(&article.title),
+        dsl::description.eq(&article.description),
+        dsl::content.eq(&article.content),
+        dsl::update_status.eq(1),
+      ))
+      .execute(conn)?;
+      // the others are refreshed silently, like the media newly parsed
+      diesel::update(
+        stored.filter(
+          dsl::url
+            .is_not(&article.url)
+            .or(dsl::author.is_not(&article.author))
+            .or(dsl::image.is_not(&article.image))
+            .or(dsl::categories.is_not(&article.categories))
+            .or(dsl::video_url.is_not(&article.video_url))
+            .or(dsl::duration.is_not(article.duration))
+            .or(dsl::season.is_not(article.season))
+            .or(dsl::episode.is_not(article.episode))
+            .or(dsl::explicit.is_not(article.explicit))
+            .or(dsl::chapters_url.is_not(&article.chapters_url))
+            .or(dsl::transcript_url.is_not(&article.transcript_url)),
+        ),
+      )
+      .set((
+        dsl::url.eq(&article.url),
+        dsl::author.eq(&article.author),
+        dsl::image.eq(&article.image),
+        dsl::categories.eq(&article.categories),
+        dsl::video_url.eq(&article.video_url),
+        dsl::duration.eq(article.duration),
+        dsl::season.eq(article.season),
+        dsl::episode.eq(article.episode),
+        dsl::explicit.eq(article.explicit),
+        dsl::chapters_url.eq(&article.chapters_url),
+        dsl::transcript_url.eq(&article.transcript_url),
+      ))
+      .execute(conn)?;
+    }
+
+    Ok(added)
+  })
+}
+
+// the rows from before v08 have the url as guid: give the row of the
+// same url its guid in the feed, instead of taking the item as new
+fn adopt_legacy_guid(
+  conn: &mut SqliteConnection,
+  article: &NewArticle,
+) -> QueryResult<usize> {
+  use schema::articles::dsl;
+
+  let existing = dsl::articles
+    .filter(dsl::feed_link.eq(&article.feed_link))
+    .filter(dsl::guid.eq(&article.guid))
+    .count()
+    .get_result::<i64>(conn)?;
+  if existing > 0 {
+    return Ok(0);
+  }
+
+  diesel::update(
+    dsl::articles
+      .filter(dsl::feed_link.eq(&article.feed_link))
+      .filter(dsl::guid.eq(&article.url))
+      .filter(dsl::url.eq(&article.url)),
+  )
+  .set(dsl::guid.eq(&article.guid))
+  .execute(conn)
+}
+
+// mark an article per the actions of the filter rules it matches
+fn apply_filter_actions(
+  conn: &mut SqliteConnection,
+  id: i32,
+  actions: &filter::Actions,
+) -> QueryResult<usize> {
+  use schema::articles::dsl;
+
+  let article = dsl::articles.find(id);
+  if actions.delete {
+    return diesel::delete(article).execute(conn);
   }
+  let mut updated = 0;
+  if actions.read || actions.hide {
+    updated += diesel::update(article)
+      .set((dsl::read_status.eq(1), dsl::update_status.eq(0)))
+      .execute(conn)?;
+  }
+  if actions.star {
+    updated += diesel::update(article)
+      .set(dsl::star_status.eq(1))
+      .execute(conn)?;
+  }
+  if actions.hide {
+    updated += diesel::update(article)
+      .set(dsl::hide_status.eq(1))
+      .execute(conn)?;
+  }
+
+  Ok(updated)
 }
 
+// the first one if the url is linked by more than one feed
 pub fn get_article_by_url(url: String) -> Option<Article> {
   let mut connection = establish_connection();
-  let mut result = schema::articles::dsl::articles
+  schema::articles::dsl::articles
     .filter(schema::articles::url.eq(&url))
-    .load::<Article>(&mut connection)
+    .order(schema::articles::id.asc())
+    .first::<Article>(&mut connection)
+    .optional()
     .map_err(|e| {
       do_log(
         "Error".to_string(),
@@ -230,13 +743,62 @@ pub fn get_article_by_url(url: String) -> Option<Article> {
         format!("{}", Local::now().format("%m/%d/%Y %H:%M:%S")),
       )
     })
-    .unwrap_or_else(|_| vec![]);
+    .unwrap_or(None)
+}
 
-  if result.len() == 1 {
-    return result.pop();
-  } else {
-    return None;
-  }
+pub fn get_article_by_id(id: i32) -> Option<Article> {
+  let mut connection = establish_connection();
+  schema::articles::dsl::articles
+    .find(id)
+    .first::<Article>(&mut connection)
+    .optional()
+    .map_err(|e| {
+      do_log(
+        "Error".to_string(),
+        format!("db Error on [get_article_by_id, {}]: {:?}", id, e),
+        format!("{}", Local::now().format("%m/%d/%Y %H:%M:%S")),
+      )
+    })
+    .unwrap_or(None)
+}
+
+pub fn update_article_read_status_by_id(id: i32, status: i32) -> usize {
+  let mut connection = establish_connection();
+  diesel::update(schema::articles::dsl::articles.find(id))
+    .set((
+      schema::articles::read_status.eq(status),
+      schema::articles::update_status.eq(0),
+    ))
+    .execute(&mut connection)
+    .map_err(|e| {
+      do_log(
+        "Error".to_string(),
+        format!(
+          "db Error on [update_article_read_status_by_id: {}]: {:?}",
+          id, e
+        ),
+        format!("{}", Local::now().format("%m/%d/%Y %H:%M:%S")),
+      )
+    })
+    .unwrap_or(0)
+}
+
+pub fn update_article_star_status_by_id(id: i32, status: i32) -> usize {
+  let mut connection = establish_connection();
+  diesel::update(schema::articles::dsl::articles.find(id))
+    .set(schema::articles::star_status.eq(status))
+    .execute(&mut connection)
+    .map_err(|e| {
+      do_log(
+        "Error".to_string(),
+        format!(
+          "db Error on [update_article_star_status_by_id, {}]: {:?}",
+          id, e
+        ),
+        format!("{}", Local::now().format("%m/%d/%Y %H:%M:%S")),
+      )
+    })
+    .unwrap_or(0)
 }
 
 pub fn update_article_read_status(url: String, status: i32) -> usize {
@@ -247,7 +809,11 @@ pub fn update_article_read_status(url: String, status: i32) -> usize {
     Some(_) => diesel::update(
       schema::articles::dsl::articles.filter(schema::articles::url.eq(&url)),
     )
-    .set(schema::articles::read_status.eq(status))
+    .set((
+      schema::articles::read_status.eq(status),
+      // the revision is seen
+      schema::articles::update_status.eq(0),
+    ))
     .execute(&mut connection)
     .map_err(|e| {
       do_log(
@@ -283,11 +849,38 @@ pub fn update_article_star_status(url: String, status: i32) -> usize {
   }
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Default, Serialize, Deserialize)]
 pub struct ArticleFilter {
   pub feed_link: Option<String>,
+  pub folder_id: Option<i32>,
   pub read_status: Option<i32>,
   pub star_status: Option<i32>,
+  // published at or after
+  pub since: Option<DateTime<Utc>>,
+  // by published, newest first by default
+  pub sort: Option<SortOrder>,
+  // page after the last article of the previous page
+  pub cursor: Option<ArticleCursor>,
+  pub offset: Option<i64>,
+  pub limit: Option<i64>,
+  // leave out the content
+  pub summary: Option<bool>,
+  // include the articles hidden by filter rules
+  pub show_hidden: Option<bool>,
+}
+
+#[derive(Debug, Default, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SortOrder {
+  #[default]
+  Desc,
+  Asc,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ArticleCursor {
+  pub published: DateTime<Utc>,
+  pub id: i32,
 }
 
 pub fn get_articles(filter: ArticleFilter) -> Vec<Article> {
@@ -300,6 +893,16 @@ pub fn get_articles(filter: ArticleFilter) -> Vec<Article> {
     query = query.filter(schema::articles::feed_link.eq(feed_link));
   }
 
+  if let Some(folder_id) = filter.folder_id {
+    query = query.filter(
+      schema::articles::feed_link.eq_any(
+        schema::channel_folders::dsl::channel_folders
+          .filter(schema::channel_folders::folder_id.eq(folder_id))
+          .select(schema::channel_folders::channel_link),
+      ),
+    );
+  }
+
   if let Some(read_status) = filter.read_status.clone() {
     // println!("read status: {:?}", read_status);
     query = query.filter(schema::articles::read_status.eq(read_status));
@@ -310,10 +913,89 @@ pub fn get_articles(filter: ArticleFilter) -> Vec<Article> {
     query = query.filter(schema::articles::star_status.eq(star_status));
   }
 
-  let result = query
-    .load::<Article>(&mut connection)
-    .map_err(|e| {
-      do_log(
+  if let Some(since) = filter.since {
+    query = query.filter(schema::articles::published.ge(since));
+  }
+
+  if !filter.show_hidden.unwrap_or(false) {
+    query = query.filter(schema::articles::hide_status.eq(0));
+  }
+
+  let sort = filter.sort.unwrap_or_default();
+  if let Some(cursor) = filter.cursor.clone() {
+    let published = schema::articles::published;
+    let id = schema::articles::id;
+    query = match sort {
+      SortOrder::Desc => query.filter(
+        published
+          .lt(cursor.published)
+          .or(published.eq(cursor.published).and(id.lt(cursor.id))),
+      ),
+      SortOrder::Asc => query.filter(
+        published
+          .gt(cursor.published)
+          .or(published.eq(cursor.published).and(id.gt(cursor.id))),
+      ),
+    };
+  }
+
+  query = match sort {
+    SortOrder::Desc => query.order((
+      schema::articles::published.desc(),
+      schema::articles::id.desc(),
+    )),
+    SortOrder::Asc => query.order((
+      schema::articles::published.asc(),
+      schema::articles::id.asc(),
+    )),
+  };
+
+  if let Some(limit) = filter.limit {
+    query = query.limit(limit);
+  }
+  if let Some(offset) = filter.offset {
+    // sqlite needs a LIMIT for OFFSET
+    query = query.limit(filter.limit.unwrap_or(-1)).offset(offset);
+  }
+
+  // the content is the bulk of an article
+  let content = if filter.summary.unwrap_or(false) {
+    "''"
+  } else {
+    "content"
+  };
+
+  let result = query
+    .select((
+      schema::articles::id,
+      schema::articles::title,
+      schema::articles::url,
+      schema::articles::feed_link,
+      schema::articles::audio_url,
+      schema::articles::description,
+      schema::articles::published,
+      diesel::dsl::sql::<diesel::sql_types::Text>(content),
+      schema::articles::author,
+      schema::articles::image,
+      schema::articles::read_status,
+      schema::articles::star_status,
+      schema::articles::update_status,
+      schema::articles::guid,
+      schema::articles::categories,
+      schema::articles::video_url,
+      schema::articles::duration,
+      schema::articles::season,
+      schema::articles::episode,
+      schema::articles::explicit,
+      schema::articles::chapters_url,
+      schema::articles::transcript_url,
+      schema::articles::local_audio_path,
+      schema::articles::hide_status,
+      schema::articles::full_status,
+    ))
+    .load::<Article>(&mut connection)
+    .map_err(|e| {
+      do_log(
         "Error".to_string(),
         format!(
           "db Error on [get_articles, per fileter: {:?}]: {:?}",
@@ -329,13 +1011,121 @@ pub fn get_articles(filter: ArticleFilter) -> Vec<Article> {
   return result;
 }
 
+#[derive(Debug, Serialize, QueryableByName)]
+pub struct ArticleSearchResult {
+  #[diesel(sql_type = diesel::sql_types::Integer)]
+  pub id: i32,
+  #[diesel(sql_type = diesel::sql_types::Text)]
+  pub title: String,
+  #[diesel(sql_type = diesel::sql_types::Text)]
+  pub url: String,
+  #[diesel(sql_type = diesel::sql_types::Text)]
+  pub feed_link: String,
+  #[diesel(sql_type = diesel::sql_types::Text)]
+  pub audio_url: String,
+  #[diesel(sql_type = diesel::sql_types::TimestamptzSqlite)]
+  pub published: DateTime<Utc>,
+  #[diesel(sql_type = diesel::sql_types::Text)]
+  pub author: String,
+  #[diesel(sql_type = diesel::sql_types::Text)]
+  pub image: String,
+  #[diesel(sql_type = diesel::sql_types::Integer)]
+  pub read_status: i32,
+  #[diesel(sql_type = diesel::sql_types::Integer)]
+  pub star_status: i32,
+  #[diesel(sql_type = diesel::sql_types::Integer)]
+  pub update_status: i32,
+  // title with the matches wrapped in <mark>
+  #[diesel(sql_type = diesel::sql_types::Text)]
+  pub title_highlight: String,
+  // matched fragment of the best column
+  #[diesel(sql_type = diesel::sql_types::Text)]
+  pub snippet: String,
+  // bm25, the lower the better
+  #[diesel(sql_type = diesel::sql_types::Double)]
+  pub rank: f64,
+}
+
+// turn user input into a fts5 query: each word as a quoted prefix,
+// so punctuation never becomes a syntax error
+pub fn to_fts_query(query: &str) -> String {
+  query
+    .split_whitespace()
+    .map(|word| format!("\"{}\"*", word.replace('"', "\"\"")))
+    .collect::<Vec<String>>()
+    .join(" ")
+}
+
+pub fn search_articles(
+  query: String,
+  filter: ArticleFilter,
+  limit: i64,
+) -> Vec<ArticleSearchResult> {
+  const SQL_SEARCH_ARTICLES: &str = "
+    SELECT a.id, a.title, a.url, a.feed_link, a.audio_url,
+      a.published,
+      coalesce(a.author, '') as author,
+      coalesce(a.image, '') as image,
+      a.read_status, a.star_status, a.update_status,
+      highlight(articles_fts, 0, '<mark>', '</mark>') as title_highlight,
+      snippet(articles_fts, -1, '<mark>', '</mark>', '...', 24) as snippet,
+      bm25(articles_fts, 10.0, 4.0, 1.0, 2.0) as rank
+    FROM articles_fts INNER JOIN articles a ON a.id = articles_fts.rowid
+    WHERE articles_fts MATCH ?
+      AND (? IS NULL OR a.feed_link = ?)
+      AND (? IS NULL OR a.feed_link IN
+        (SELECT channel_link FROM channel_folders WHERE folder_id = ?))
+      AND (? IS NULL OR a.read_status = ?)
+      AND (? IS NULL OR a.star_status = ?)
+      AND (? IS NULL OR a.published >= ?)
+      AND (? OR a.hide_status = 0)
+    ORDER BY rank LIMIT ?;
+    ";
+  use diesel::sql_types::{
+    BigInt, Bool, Integer, Nullable, Text, TimestamptzSqlite,
+  };
+
+  let fts_query = to_fts_query(&query);
+  if fts_query.is_empty() {
+    return vec![];
+  }
+
+  let mut connection = establish_connection();
+  diesel::sql_query(SQL_SEARCH_ARTICLES)
+    .bind::<Text, _>(&fts_query)
+    .bind::<Nullable<Text>, _>(&filter.feed_link)
+    .bind::<Nullable<Text>, _>(&filter.feed_link)
+    .bind::<Nullable<Integer>, _>(filter.folder_id)
+    .bind::<Nullable<Integer>, _>(filter.folder_id)
+    .bind::<Nullable<Integer>, _>(filter.read_status)
+    .bind::<Nullable<Integer>, _>(filter.read_status)
+    .bind::<Nullable<Integer>, _>(filter.star_status)
+    .bind::<Nullable<Integer>, _>(filter.star_status)
+    .bind::<Nullable<TimestamptzSqlite>, _>(filter.since)
+    .bind::<Nullable<TimestamptzSqlite>, _>(filter.since)
+    .bind::<Bool, _>(filter.show_hidden.unwrap_or(false))
+    .bind::<BigInt, _>(limit)
+    .load::<ArticleSearchResult>(&mut connection)
+    .map_err(|e| {
+      do_log(
+        "Error".to_string(),
+        format!("db Error on [search_articles, {}]: {:?}", query, e),
+        format!("{}", Local::now().format("%m/%d/%Y %H:%M:%S")),
+      )
+    })
+    .unwrap_or_else(|_| vec![])
+}
+
 pub fn update_articles_read_status(feed_link: String, read_status: i32) -> usize {
   let mut connection = establish_connection();
   let result = diesel::update(
     schema::articles::dsl::articles
       .filter(schema::articles::feed_link.eq(feed_link)),
   )
-  .set(schema::articles::read_status.eq(read_status))
+  .set((
+    schema::articles::read_status.eq(read_status),
+    schema::articles::update_status.eq(0),
+  ))
   .execute(&mut connection)
   .map_err(|e| {
     do_log(
@@ -349,6 +1139,938 @@ pub fn update_articles_read_status(feed_link: String, read_status: i32) -> usize
   return result;
 }
 
+pub fn mark_all_read() -> usize {
+  let mut connection = establish_connection();
+  diesel::update(
+    schema::articles::dsl::articles.filter(schema::articles::read_status.eq(0)),
+  )
+  .set((
+    schema::articles::read_status.eq(1),
+    schema::articles::update_status.eq(0),
+  ))
+  .execute(&mut connection)
+  .map_err(|e| {
+    do_log(
+      "Error".to_string(),
+      format!("db Error on [mark_all_read]: {:?}", e),
+      format!("{}", Local::now().format("%m/%d/%Y %H:%M:%S")),
+    )
+  })
+  .unwrap_or(0)
+}
+
+pub fn get_folders() -> Vec<Folder> {
+  let mut connection = establish_connection();
+  schema::folders::dsl::folders
+    .order(schema::folders::name.asc())
+    .load::<Folder>(&mut connection)
+    .map_err(|e| {
+      do_log(
+        "Error".to_string(),
+        format!("db Error on [get_folders]: {:?}", e),
+        format!("{}", Local::now().format("%m/%d/%Y %H:%M:%S")),
+      )
+    })
+    .unwrap_or_else(|_| vec![])
+}
+
+// create if not existing, get the folder per name
+pub fn create_folder(name: String) -> Option<Folder> {
+  let mut connection = establish_connection();
+  diesel::insert_or_ignore_into(schema::folders::dsl::folders)
+    .values(schema::folders::name.eq(&name))
+    .execute(&mut connection)
+    .map_err(|e| {
+      do_log(
+        "Error".to_string(),
+        format!("db Error on [create_folder: insert, {}]: {:?}", name, e),
+        format!("{}", Local::now().format("%m/%d/%Y %H:%M:%S")),
+      )
+    })
+    .ok()?;
+
+  schema::folders::dsl::folders
+    .filter(schema::folders::name.eq(&name))
+    .first::<Folder>(&mut connection)
+    .map_err(|e| {
+      do_log(
+        "Error".to_string(),
+        format!("db Error on [create_folder: query, {}]: {:?}", name, e),
+        format!("{}", Local::now().format("%m/%d/%Y %H:%M:%S")),
+      )
+    })
+    .ok()
+}
+
+pub fn rename_folder(id: i32, name: String) -> usize {
+  let mut connection = establish_connection();
+  diesel::update(schema::folders::dsl::folders.find(id))
+    .set(schema::folders::name.eq(name))
+    .execute(&mut connection)
+    .map_err(|e| {
+      do_log(
+        "Error".to_string(),
+        format!("db Error on [rename_folder, {}]: {:?}", id, e),
+        format!("{}", Local::now().format("%m/%d/%Y %H:%M:%S")),
+      )
+    })
+    .unwrap_or(0)
+}
+
+// del folder, its channels are kept without folder
+pub fn delete_folder(id: i32) -> usize {
+  let mut connection = establish_connection();
+  diesel::delete(
+    schema::channel_folders::dsl::channel_folders
+      .filter(schema::channel_folders::folder_id.eq(id)),
+  )
+  .execute(&mut connection)
+  .map_err(|e| {
+    do_log(
+      "Error".to_string(),
+      format!(
+        "db Error on [delete_folder: del channel links, {}]: {:?}",
+        id, e
+      ),
+      format!("{}", Local::now().format("%m/%d/%Y %H:%M:%S")),
+    )
+  })
+  .unwrap_or(0);
+
+  diesel::delete(schema::folders::dsl::folders.find(id))
+    .execute(&mut connection)
+    .map_err(|e| {
+      do_log(
+        "Error".to_string(),
+        format!("db Error on [delete_folder, {}]: {:?}", id, e),
+        format!("{}", Local::now().format("%m/%d/%Y %H:%M:%S")),
+      )
+    })
+    .unwrap_or(0)
+}
+
+// move channel to a folder, or out of any folder on None
+// 0 on an unknown folder
+pub fn set_channel_folder(link: String, folder_id: Option<i32>) -> usize {
+  let mut connection = establish_connection();
+  let result = match folder_id {
+    Some(folder_id) => connection.immediate_transaction(|conn| {
+      let exists = schema::folders::dsl::folders
+        .find(folder_id)
+        .count()
+        .get_result::<i64>(conn)?;
+      if exists == 0 {
+        return Ok(0);
+      }
+      diesel::replace_into(schema::channel_folders::dsl::channel_folders)
+        .values((
+          schema::channel_folders::channel_link.eq(&link),
+          schema::channel_folders::folder_id.eq(folder_id),
+        ))
+        .execute(conn)
+    }),
+    None => diesel::delete(
+      schema::channel_folders::dsl::channel_folders
+        .filter(schema::channel_folders::channel_link.eq(&link)),
+    )
+    .execute(&mut connection),
+  };
+
+  result
+    .map_err(|e| {
+      do_log(
+        "Error".to_string(),
+        format!("db Error on [set_channel_folder, {}]: {:?}", link, e),
+        format!("{}", Local::now().format("%m/%d/%Y %H:%M:%S")),
+      )
+    })
+    .unwrap_or(0)
+}
+
+// pairs of (channel link, folder id)
+pub fn get_channel_folders() -> Vec<(String, i32)> {
+  let mut connection = establish_connection();
+  schema::channel_folders::dsl::channel_folders
+    .select((
+      schema::channel_folders::channel_link,
+      schema::channel_folders::folder_id,
+    ))
+    .load::<(String, i32)>(&mut connection)
+    .map_err(|e| {
+      do_log(
+        "Error".to_string(),
+        format!("db Error on [get_channel_folders]: {:?}", e),
+        format!("{}", Local::now().format("%m/%d/%Y %H:%M:%S")),
+      )
+    })
+    .unwrap_or_else(|_| vec![])
+}
+
+pub fn get_channel_retention(link: String) -> Option<RetentionPolicy> {
+  let mut connection = establish_connection();
+  schema::channel_retention::dsl::channel_retention
+    .filter(schema::channel_retention::channel_link.eq(&link))
+    .select((
+      schema::channel_retention::keep_num,
+      schema::channel_retention::keep_days,
+      schema::channel_retention::keep_unread,
+    ))
+    .first::<RetentionPolicy>(&mut connection)
+    .optional()
+    .map_err(|e| {
+      do_log(
+        "Error".to_string(),
+        format!("db Error on [get_channel_retention, {}]: {:?}", link, e),
+        format!("{}", Local::now().format("%m/%d/%Y %H:%M:%S")),
+      )
+    })
+    .unwrap_or(None)
+}
+
+// set the retention of a channel, or follow the global one on None
+pub fn set_channel_retention(
+  link: String,
+  policy: Option<RetentionPolicy>,
+) -> usize {
+  let mut connection = establish_connection();
+  let result = match policy {
+    Some(policy) => {
+      diesel::replace_into(schema::channel_retention::dsl::channel_retention)
+        .values((
+          schema::channel_retention::channel_link.eq(&link),
+          schema::channel_retention::keep_num.eq(policy.keep_num),
+          schema::channel_retention::keep_days.eq(policy.keep_days),
+          schema::channel_retention::keep_unread.eq(policy.keep_unread),
+        ))
+        .execute(&mut connection)
+    }
+    None => diesel::delete(
+      schema::channel_retention::dsl::channel_retention
+        .filter(schema::channel_retention::channel_link.eq(&link)),
+    )
+    .execute(&mut connection),
+  };
+
+  result
+    .map_err(|e| {
+      do_log(
+        "Error".to_string(),
+        format!("db Error on [set_channel_retention, {}]: {:?}", link, e),
+        format!("{}", Local::now().format("%m/%d/%Y %H:%M:%S")),
+      )
+    })
+    .unwrap_or(0)
+}
+
+// the secret decrypted, empty if it can not be
+pub fn get_channel_auth(link: String) -> Option<ChannelAuth> {
+  let mut connection = establish_connection();
+  let auth = schema::channel_auth::dsl::channel_auth
+    .filter(schema::channel_auth::channel_link.eq(&link))
+    .select((
+      schema::channel_auth::kind,
+      schema::channel_auth::username,
+      schema::channel_auth::secret,
+    ))
+    .first::<ChannelAuth>(&mut connection)
+    .optional()
+    .map_err(|e| {
+      do_log(
+        "Error".to_string(),
+        format!("db Error on [get_channel_auth, {}]: {:?}", link, e),
+        format!("{}", Local::now().format("%m/%d/%Y %H:%M:%S")),
+      )
+    })
+    .unwrap_or(None)?;
+
+  let stored = auth.secret.clone();
+  let auth = ChannelAuth {
+    secret: secret::decrypt(&stored).unwrap_or_default(),
+    ..auth
+  };
+  // stored in plain before, encrypt it now
+  if !stored.is_empty() && !secret::is_encrypted(&stored) {
+    set_channel_auth(link, Some(auth.clone()));
+  }
+
+  Some(auth)
+}
+
+// set the credentials of a channel, or remove them on None;
+// the stored secret is kept on an empty one, it is never sent back
+// to the webview to be edited
+pub fn set_channel_auth(link: String, auth: Option<ChannelAuth>) -> usize {
+  use schema::channel_auth::dsl;
+
+  let mut connection = establish_connection();
+  let result = match auth {
+    Some(auth) => connection.immediate_transaction(|conn| {
+      let stored = if auth.secret.is_empty() {
+        dsl::channel_auth
+          .find(&link)
+          .select(dsl::secret)
+          .first::<String>(conn)
+          .optional()?
+          .unwrap_or_default()
+      } else {
+        match secret::encrypt(&auth.secret) {
+          Some(encrypted) => encrypted,
+          // not stored in plain
+          None => return Ok(0),
+        }
+      };
+      diesel::replace_into(dsl::channel_auth)
+        .values((
+          dsl::channel_link.eq(&link),
+          dsl::kind.eq(&auth.kind),
+          dsl::username.eq(&auth.username),
+          dsl::secret.eq(stored),
+        ))
+        .execute(conn)
+    }),
+    None => diesel::delete(dsl::channel_auth.filter(dsl::channel_link.eq(&link)))
+      .execute(&mut connection),
+  };
+
+  result
+    .map_err(|e| {
+      do_log(
+        "Error".to_string(),
+        format!("db Error on [set_channel_auth, {}]: {:?}", link, e),
+        format!("{}", Local::now().format("%m/%d/%Y %H:%M:%S")),
+      )
+    })
+    .unwrap_or(0)
+}
+
+// delete the articles of a channel out of the retention,
+// get the number of deleted articles
+pub fn prune_articles(link: String, policy: &RetentionPolicy) -> usize {
+  // nothing to prune if keep all
+  if policy.keep_num.is_none() && policy.keep_days.is_none() {
+    return 0;
+  }
+
+  let mut connection = establish_connection();
+  prune(&mut connection, &link, policy)
+    .map_err(|e| {
+      do_log(
+        "Error".to_string(),
+        format!("db Error on [prune_articles, {}]: {:?}", link, e),
+        format!("{}", Local::now().format("%m/%d/%Y %H:%M:%S")),
+      )
+    })
+    .unwrap_or(0)
+}
+
+// the guids of the pruned are kept, not to fetch them again as new
+fn prune(
+  connection: &mut SqliteConnection,
+  link: &str,
+  policy: &RetentionPolicy,
+) -> QueryResult<usize> {
+  const SQL_PRUNE_ARTICLES: &str = "
+    INSERT OR IGNORE INTO pruned_articles (feed_link, guid)
+    SELECT feed_link, guid FROM articles
+    WHERE feed_link = ? AND star_status = 0
+      AND (? = 0 OR read_status = 1)
+      AND (? IS NULL OR id NOT IN (
+        SELECT id FROM articles WHERE feed_link = ?
+        ORDER BY published DESC, id DESC LIMIT ?))
+      AND (? IS NULL OR published < ?);
+    ";
+  const SQL_DELETE_PRUNED: &str = "
+    DELETE FROM articles
+    WHERE feed_link = ? AND star_status = 0
+      AND guid IN (SELECT guid FROM pruned_articles WHERE feed_link = ?);
+    ";
+  use diesel::sql_types::{Bool, Integer, Nullable, Text, TimestamptzSqlite};
+
+  let before = policy
+    .keep_days
+    .map(|days| date::now() - chrono::Duration::days(days.max(0).into()));
+
+  connection.immediate_transaction(|conn| {
+    diesel::sql_query(SQL_PRUNE_ARTICLES)
+      .bind::<Text, _>(link)
+      .bind::<Bool, _>(policy.keep_unread)
+      .bind::<Nullable<Integer>, _>(policy.keep_num)
+      .bind::<Text, _>(link)
+      .bind::<Integer, _>(policy.keep_num.unwrap_or(0).max(0))
+      .bind::<Nullable<TimestamptzSqlite>, _>(before)
+      .bind::<Nullable<TimestamptzSqlite>, _>(before)
+      .execute(conn)?;
+    diesel::sql_query(SQL_DELETE_PRUNED)
+      .bind::<Text, _>(link)
+      .bind::<Text, _>(link)
+      .execute(conn)
+  })
+}
+
+#[derive(Debug, QueryableByName)]
+struct PageCount {
+  #[diesel(sql_type = diesel::sql_types::BigInt)]
+  page_count: i64,
+  #[diesel(sql_type = diesel::sql_types::BigInt)]
+  freelist_count: i64,
+}
+
+// reclaim the space of deleted articles once a quarter of db is free
+pub fn vacuum_if_needed() -> bool {
+  const SQL_PAGE_COUNT: &str = "
+    SELECT page_count, freelist_count
+    FROM pragma_page_count(), pragma_freelist_count();
+    ";
+
+  let mut connection = establish_connection();
+  let needed = diesel::sql_query(SQL_PAGE_COUNT)
+    .get_result::<PageCount>(&mut connection)
+    .map(|c| c.freelist_count * 4 > c.page_count)
+    .unwrap_or(false);
+  if !needed {
+    return false;
+  }
+
+  connection
+    .batch_execute("VACUUM;")
+    .map_err(|e| {
+      do_log(
+        "Error".to_string(),
+        format!("db Error on [vacuum]: {:?}", e),
+        format!("{}", Local::now().format("%m/%d/%Y %H:%M:%S")),
+      )
+    })
+    .is_ok()
+}
+
+// # podcast playback #
+
+// save the position of an episode, keep the duration if None
+pub fn save_playback(
+  audio_url: String,
+  position: f64,
+  duration: Option<f64>,
+) -> usize {
+  const SQL_SAVE_PLAYBACK: &str = "
+    INSERT INTO playback (audio_url, position, duration, updated_at)
+    VALUES (?, ?, ?, ?)
+    ON CONFLICT (audio_url) DO UPDATE SET
+      position = excluded.position,
+      duration = coalesce(excluded.duration, duration),
+      updated_at = excluded.updated_at;
+    ";
+  use diesel::sql_types::{Double, Nullable, Text, TimestamptzSqlite};
+
+  let mut connection = establish_connection();
+  diesel::sql_query(SQL_SAVE_PLAYBACK)
+    .bind::<Text, _>(&audio_url)
+    .bind::<Double, _>(position.max(0.0))
+    .bind::<Nullable<Double>, _>(duration)
+    .bind::<TimestamptzSqlite, _>(date::now())
+    .execute(&mut connection)
+    .map_err(|e| {
+      do_log(
+        "Error".to_string(),
+        format!("db Error on [save_playback, {}]: {:?}", audio_url, e),
+        format!("{}", Local::now().format("%m/%d/%Y %H:%M:%S")),
+      )
+    })
+    .unwrap_or(0)
+}
+
+pub fn set_playback_completed(audio_url: String, completed: bool) -> usize {
+  let mut connection = establish_connection();
+  diesel::insert_into(schema::playback::dsl::playback)
+    .values((
+      schema::playback::audio_url.eq(&audio_url),
+      schema::playback::completed.eq(completed),
+      schema::playback::updated_at.eq(date::now()),
+    ))
+    .on_conflict(schema::playback::audio_url)
+    .do_update()
+    .set((
+      schema::playback::completed.eq(completed),
+      schema::playback::updated_at.eq(date::now()),
+    ))
+    .execute(&mut connection)
+    .map_err(|e| {
+      do_log(
+        "Error".to_string(),
+        format!(
+          "db Error on [set_playback_completed, {}]: {:?}",
+          audio_url, e
+        ),
+        format!("{}", Local::now().format("%m/%d/%Y %H:%M:%S")),
+      )
+    })
+    .unwrap_or(0)
+}
+
+pub fn get_playback(audio_url: String) -> Option<Playback> {
+  let mut connection = establish_connection();
+  schema::playback::dsl::playback
+    .find(&audio_url)
+    .first::<Playback>(&mut connection)
+    .optional()
+    .map_err(|e| {
+      do_log(
+        "Error".to_string(),
+        format!("db Error on [get_playback, {}]: {:?}", audio_url, e),
+        format!("{}", Local::now().format("%m/%d/%Y %H:%M:%S")),
+      )
+    })
+    .unwrap_or(None)
+}
+
+// the recently played first
+pub fn get_playbacks() -> Vec<Playback> {
+  let mut connection = establish_connection();
+  schema::playback::dsl::playback
+    .order(schema::playback::updated_at.desc())
+    .load::<Playback>(&mut connection)
+    .map_err(|e| {
+      do_log(
+        "Error".to_string(),
+        format!("db Error on [get_playbacks]: {:?}", e),
+        format!("{}", Local::now().format("%m/%d/%Y %H:%M:%S")),
+      )
+    })
+    .unwrap_or_else(|_| vec![])
+}
+
+pub fn get_play_queue() -> Vec<QueueItem> {
+  let mut connection = establish_connection();
+  schema::play_queue::dsl::play_queue
+    .order(schema::play_queue::queue_order.asc())
+    .load::<QueueItem>(&mut connection)
+    .map_err(|e| {
+      do_log(
+        "Error".to_string(),
+        format!("db Error on [get_play_queue]: {:?}", e),
+        format!("{}", Local::now().format("%m/%d/%Y %H:%M:%S")),
+      )
+    })
+    .unwrap_or_else(|_| vec![])
+}
+
+// take out of the queue, closing the gap
+fn dequeue(conn: &mut SqliteConnection, audio_url: &str) -> QueryResult<usize> {
+  use schema::play_queue::dsl;
+
+  let order = dsl::play_queue
+    .find(audio_url)
+    .select(dsl::queue_order)
+    .first::<i32>(conn)
+    .optional()?;
+  match order {
+    Some(order) => {
+      diesel::delete(dsl::play_queue.find(audio_url)).execute(conn)?;
+      diesel::update(dsl::play_queue.filter(dsl::queue_order.gt(order)))
+        .set(dsl::queue_order.eq(dsl::queue_order - 1))
+        .execute(conn)
+    }
+    None => Ok(0),
+  }
+}
+
+// put an episode at the index of the queue, or at the end on None;
+// an episode already queued is moved
+fn enqueue(
+  conn: &mut SqliteConnection,
+  item: &QueueItem,
+  index: Option<i32>,
+) -> QueryResult<usize> {
+  use schema::play_queue::dsl;
+
+  dequeue(conn, &item.audio_url)?;
+  let len = dsl::play_queue.count().get_result::<i64>(conn)? as i32;
+  let order = index.map(|i| i.clamp(0, len)).unwrap_or(len);
+  diesel::update(dsl::play_queue.filter(dsl::queue_order.ge(order)))
+    .set(dsl::queue_order.eq(dsl::queue_order + 1))
+    .execute(conn)?;
+  diesel::insert_into(dsl::play_queue)
+    .values(QueueItem {
+      queue_order: order,
+      ..item.clone()
+    })
+    .execute(conn)
+}
+
+pub fn add_to_queue(item: QueueItem, index: Option<i32>) -> usize {
+  let mut connection = establish_connection();
+  connection
+    .immediate_transaction(|conn| enqueue(conn, &item, index))
+    .map_err(|e| {
+      do_log(
+        "Error".to_string(),
+        format!("db Error on [add_to_queue, {}]: {:?}", item.audio_url, e),
+        format!("{}", Local::now().format("%m/%d/%Y %H:%M:%S")),
+      )
+    })
+    .unwrap_or(0)
+}
+
+pub fn remove_from_queue(audio_url: String) -> usize {
+  let mut connection = establish_connection();
+  connection
+    .immediate_transaction(|conn| dequeue(conn, &audio_url))
+    .map_err(|e| {
+      do_log(
+        "Error".to_string(),
+        format!("db Error on [remove_from_queue, {}]: {:?}", audio_url, e),
+        format!("{}", Local::now().format("%m/%d/%Y %H:%M:%S")),
+      )
+    })
+    .unwrap_or(0)
+}
+
+// order the queue per the audio urls, the ones not listed go after
+fn reorder(
+  conn: &mut SqliteConnection,
+  audio_urls: &[String],
+) -> QueryResult<usize> {
+  use schema::play_queue::dsl;
+
+  let queued = dsl::play_queue
+    .order(dsl::queue_order.asc())
+    .select(dsl::audio_url)
+    .load::<String>(conn)?;
+  let (mut ordered, rest): (Vec<String>, Vec<String>) =
+    queued.into_iter().partition(|url| audio_urls.contains(url));
+  ordered.sort_by_key(|url| audio_urls.iter().position(|u| u == url));
+  ordered.extend(rest);
+
+  let mut num = 0;
+  for (order, url) in ordered.iter().enumerate() {
+    num += diesel::update(dsl::play_queue.find(url))
+      .set(dsl::queue_order.eq(order as i32))
+      .execute(conn)?;
+  }
+
+  Ok(num)
+}
+
+pub fn reorder_queue(audio_urls: Vec<String>) -> usize {
+  let mut connection = establish_connection();
+  connection
+    .immediate_transaction(|conn| reorder(conn, &audio_urls))
+    .map_err(|e| {
+      do_log(
+        "Error".to_string(),
+        format!("db Error on [reorder_queue]: {:?}", e),
+        format!("{}", Local::now().format("%m/%d/%Y %H:%M:%S")),
+      )
+    })
+    .unwrap_or(0)
+}
+
+pub fn clear_queue() -> usize {
+  let mut connection = establish_connection();
+  diesel::delete(schema::play_queue::dsl::play_queue)
+    .execute(&mut connection)
+    .map_err(|e| {
+      do_log(
+        "Error".to_string(),
+        format!("db Error on [clear_queue]: {:?}", e),
+        format!("{}", Local::now().format("%m/%d/%Y %H:%M:%S")),
+      )
+    })
+    .unwrap_or(0)
+}
+
+// the content extracted from the article page
+pub fn set_article_full_content(id: i32, content: String) -> usize {
+  let mut connection = establish_connection();
+  diesel::update(schema::articles::dsl::articles.find(id))
+    .set((
+      schema::articles::content.eq(&content),
+      schema::articles::full_status.eq(1),
+    ))
+    .execute(&mut connection)
+    .map_err(|e| {
+      do_log(
+        "Error".to_string(),
+        format!("db Error on [set_article_full_content, {}]: {:?}", id, e),
+        format!("{}", Local::now().format("%m/%d/%Y %H:%M:%S")),
+      )
+    })
+    .unwrap_or(0)
+}
+
+pub fn set_local_audio_path(id: i32, path: String) -> usize {
+  let mut connection = establish_connection();
+  diesel::update(schema::articles::dsl::articles.find(id))
+    .set(schema::articles::local_audio_path.eq(&path))
+    .execute(&mut connection)
+    .map_err(|e| {
+      do_log(
+        "Error".to_string(),
+        format!("db Error on [set_local_audio_path, {}]: {:?}", id, e),
+        format!("{}", Local::now().format("%m/%d/%Y %H:%M:%S")),
+      )
+    })
+    .unwrap_or(0)
+}
+
+#[derive(Debug, QueryableByName)]
+pub struct DownloadedEpisode {
+  #[diesel(sql_type = diesel::sql_types::Integer)]
+  pub id: i32,
+  #[diesel(sql_type = diesel::sql_types::Text)]
+  pub local_audio_path: String,
+  #[diesel(sql_type = diesel::sql_types::Bool)]
+  pub completed: bool,
+}
+
+// the played ones first, the earliest played first
+pub fn get_downloaded_episodes() -> Vec<DownloadedEpisode> {
+  const SQL_DOWNLOADED_EPISODES: &str = "
+    SELECT a.id, a.local_audio_path, coalesce(p.completed, 0) as completed
+    FROM articles a LEFT JOIN playback p ON p.audio_url = a.audio_url
+    WHERE a.local_audio_path != ''
+    ORDER BY completed DESC, p.updated_at ASC;
+    ";
+
+  let mut connection = establish_connection();
+  diesel::sql_query(SQL_DOWNLOADED_EPISODES)
+    .load::<DownloadedEpisode>(&mut connection)
+    .map_err(|e| {
+      do_log(
+        "Error".to_string(),
+        format!("db Error on [get_downloaded_episodes]: {:?}", e),
+        format!("{}", Local::now().format("%m/%d/%Y %H:%M:%S")),
+      )
+    })
+    .unwrap_or_else(|_| vec![])
+}
+
+// # end podcast playback #
+
+// # filter rules #
+
+pub fn get_filter_rules() -> Vec<FilterRule> {
+  let mut connection = establish_connection();
+  schema::filter_rules::dsl::filter_rules
+    .order(schema::filter_rules::id.asc())
+    .load::<FilterRule>(&mut connection)
+    .map_err(|e| {
+      do_log(
+        "Error".to_string(),
+        format!("db Error on [get_filter_rules]: {:?}", e),
+        format!("{}", Local::now().format("%m/%d/%Y %H:%M:%S")),
+      )
+    })
+    .unwrap_or_else(|_| vec![])
+}
+
+pub fn create_filter_rule(rule: NewFilterRule) -> Option<FilterRule> {
+  use schema::filter_rules::dsl;
+
+  let mut connection = establish_connection();
+  connection
+    .immediate_transaction(|conn| {
+      diesel::insert_into(dsl::filter_rules)
+        .values(&rule)
+        .execute(conn)?;
+      dsl::filter_rules
+        .order(dsl::id.desc())
+        .first::<FilterRule>(conn)
+    })
+    .map_err(|e| {
+      do_log(
+        "Error".to_string(),
+        format!("db Error on [create_filter_rule, {:?}]: {:?}", rule, e),
+        format!("{}", Local::now().format("%m/%d/%Y %H:%M:%S")),
+      )
+    })
+    .ok()
+}
+
+pub fn delete_filter_rule(id: i32) -> usize {
+  let mut connection = establish_connection();
+  diesel::delete(schema::filter_rules::dsl::filter_rules.find(id))
+    .execute(&mut connection)
+    .map_err(|e| {
+      do_log(
+        "Error".to_string(),
+        format!("db Error on [delete_filter_rule, {}]: {:?}", id, e),
+        format!("{}", Local::now().format("%m/%d/%Y %H:%M:%S")),
+      )
+    })
+    .unwrap_or(0)
+}
+
+// the fields of an article the rules match, and its status
+#[derive(Debug, Queryable)]
+struct FilterRow {
+  id: i32,
+  feed_link: String,
+  title: String,
+  description: String,
+  content: String,
+  author: String,
+  categories: String,
+  read_status: i32,
+  star_status: i32,
+  hide_status: i32,
+}
+
+impl<'a> From<&'a FilterRow> for filter::Item<'a> {
+  fn from(row: &'a FilterRow) -> Self {
+    filter::Item {
+      feed_link: &row.feed_link,
+      title: &row.title,
+      description: &row.description,
+      content: &row.content,
+      author: &row.author,
+      categories: &row.categories,
+    }
+  }
+}
+
+type FilterColumns = (
+  schema::articles::id,
+  schema::articles::feed_link,
+  schema::articles::title,
+  schema::articles::description,
+  schema::articles::content,
+  schema::articles::author,
+  schema::articles::categories,
+  schema::articles::read_status,
+  schema::articles::star_status,
+  schema::articles::hide_status,
+);
+
+const FILTER_COLUMNS: FilterColumns = (
+  schema::articles::id,
+  schema::articles::feed_link,
+  schema::articles::title,
+  schema::articles::description,
+  schema::articles::content,
+  schema::articles::author,
+  schema::articles::categories,
+  schema::articles::read_status,
+  schema::articles::star_status,
+  schema::articles::hide_status,
+);
+
+// articles matched per batch, not all loaded at once
+const FILTER_BATCH: i64 = 500;
+
+// the newest articles matching a rule, at most the limit
+pub fn match_filter_rule(rule: &filter::Rule, limit: usize) -> Vec<Article> {
+  use schema::articles::dsl;
+
+  let conn = &mut establish_connection();
+  let result = (|| {
+    let mut matched: Vec<i32> = vec![];
+    let mut before = i32::MAX;
+    while matched.len() < limit {
+      let rows = dsl::articles
+        .filter(dsl::id.lt(before))
+        .order(dsl::id.desc())
+        .limit(FILTER_BATCH)
+        .select(FILTER_COLUMNS)
+        .load::<FilterRow>(conn)?;
+      let last = match rows.last() {
+        Some(row) => row.id,
+        None => break,
+      };
+      matched.extend(
+        rows
+          .iter()
+          .filter(|row| rule.matches(&filter::Item::from(*row)))
+          .map(|row| row.id),
+      );
+      before = last;
+    }
+    matched.truncate(limit);
+
+    dsl::articles
+      .filter(dsl::id.eq_any(matched))
+      .order((dsl::published.desc(), dsl::id.desc()))
+      .load::<Article>(conn)
+  })();
+
+  result
+    .map_err(|e| {
+      do_log(
+        "Error".to_string(),
+        format!("db Error on [match_filter_rule]: {:?}", e),
+        format!("{}", Local::now().format("%m/%d/%Y %H:%M:%S")),
+      )
+    })
+    .unwrap_or_else(|_| vec![])
+}
+
+#[derive(Debug, Default, Serialize)]
+pub struct FilterSummary {
+  pub read_num: usize,
+  pub star_num: usize,
+  pub hide_num: usize,
+  pub delete_num: usize,
+}
+
+// apply the rules to the existing articles, starred ones are not deleted;
+// a transaction per batch, not blocking the refreshes meanwhile
+pub fn apply_filter_rules(rules: &filter::RuleSet) -> FilterSummary {
+  use schema::articles::dsl;
+
+  let mut summary = FilterSummary::default();
+  if rules.is_empty() {
+    return summary;
+  }
+  let mut connection = establish_connection();
+  let mut after = 0;
+  loop {
+    let result = connection.immediate_transaction(|conn| {
+      let rows = dsl::articles
+        .filter(dsl::id.gt(after))
+        .order(dsl::id.asc())
+        .limit(FILTER_BATCH)
+        .select(FILTER_COLUMNS)
+        .load::<FilterRow>(conn)?;
+      for row in rows.iter() {
+        let mut actions = rules.actions(&filter::Item::from(row));
+        actions.read &= row.read_status == 0;
+        actions.star &= row.star_status == 0;
+        actions.hide &= row.hide_status == 0;
+        actions.delete &= row.star_status == 0;
+        if !actions.any() {
+          continue;
+        }
+
+        apply_filter_actions(conn, row.id, &actions)?;
+        if actions.delete {
+          summary.delete_num += 1;
+          continue;
+        }
+        summary.read_num += actions.read as usize;
+        summary.star_num += actions.star as usize;
+        summary.hide_num += actions.hide as usize;
+      }
+      diesel::QueryResult::Ok(rows.last().map(|row| row.id))
+    });
+
+    match result {
+      Ok(Some(last)) => after = last,
+      Ok(None) => break,
+      Err(e) => {
+        do_log(
+          "Error".to_string(),
+          format!("db Error on [apply_filter_rules]: {:?}", e),
+          format!("{}", Local::now().format("%m/%d/%Y %H:%M:%S")),
+        );
+        break;
+      }
+    }
+  }
+
+  summary
+}
+
+// # end filter rules #
+
 /* pub fn save_notes(notes: Note) -> usize {
   let mut connection = establish_connection();
 
@@ -387,9 +2109,194 @@ pub fn get_notes_by_id(id: String) -> Option<Note> {
 #[cfg(test)]
 mod tests {
   use super::*;
+  use diesel_migrations::MigrationHarness;
+
+  // a fresh db per test, not the user data
+  fn memory_connection() -> SqliteConnection {
+    let mut connection = SqliteConnection::establish(":memory:").unwrap();
+    connection.run_pending_migrations(crate::MIGRATIONS).unwrap();
+    connection
+  }
+
+  fn new_article(guid: &str, title: &str) -> NewArticle {
+    NewArticle {
+      title: title.to_string(),
+      url: format!("https://mdsilo.com/{}", guid),
+      feed_link: "https://mdsilo.com/rss.xml".to_string(),
+      audio_url: String::new(),
+      description: String::new(),
+      content: format!("<p>{}</p>", title),
+      published: date::now(),
+      author: String::new(),
+      image: String::new(),
+      guid: guid.to_string(),
+      categories: String::new(),
+      video_url: String::new(),
+      duration: None,
+      season: None,
+      episode: None,
+      explicit: false,
+      chapters_url: String::new(),
+      transcript_url: String::new(),
+    }
+  }
+
+  fn titles(added: QueryResult<Vec<AddedArticle>>) -> Vec<String> {
+    added.unwrap().into_iter().map(|a| a.title).collect()
+  }
+
+  fn load_articles(conn: &mut SqliteConnection) -> Vec<(String, String, i32)> {
+    use schema::articles::dsl;
+    dsl::articles
+      .order(dsl::id.asc())
+      .select((dsl::guid, dsl::title, dsl::update_status))
+      .load(conn)
+      .unwrap()
+  }
 
   #[test]
   fn test_get_unread_num() {
     get_unread_num();
+    get_folder_unread_num();
+  }
+
+  #[test]
+  fn test_to_fts_query() {
+    assert_eq!(to_fts_query("  rust "), "\"rust\"*");
+    assert_eq!(
+      to_fts_query("c++ \"tauri\" -app"),
+      "\"c++\"* \"\"\"tauri\"\"\"* \"-app\"*"
+    );
+    assert_eq!(to_fts_query("   "), "");
+  }
+
+  #[test]
+  fn test_upsert_legacy_guid() {
+    use schema::articles::dsl;
+
+    // a row as upgraded by v08, the url as guid
+    let mut conn = memory_connection();
+    let mut legacy = new_article("a", "A");
+    legacy.guid = legacy.url.clone();
+    upsert_articles(&mut conn, vec![legacy]).unwrap();
+    diesel::update(dsl::articles)
+      .set((dsl::read_status.eq(1), dsl::star_status.eq(1)))
+      .execute(&mut conn)
+      .unwrap();
+
+    // refreshed with its guid in the feed, not taken as new
+    let refreshed = vec![new_article("a", "A"), new_article("b", "B")];
+    assert_eq!(titles(upsert_articles(&mut conn, refreshed)), vec!["B"]);
+    let rows = dsl::articles
+      .order(dsl::id.asc())
+      .select((dsl::guid, dsl::read_status, dsl::star_status))
+      .load::<(String, i32, i32)>(&mut conn)
+      .unwrap();
+    assert_eq!(rows, vec![("a".into(), 1, 1), ("b".into(), 0, 0)]);
+  }
+
+  #[test]
+  fn test_prune_articles() {
+    use schema::articles::dsl;
+
+    let mut conn = memory_connection();
+    let articles = vec![new_article("a", "A"), new_article("b", "B")];
+    upsert_articles(&mut conn, articles.clone()).unwrap();
+    let policy = RetentionPolicy {
+      keep_num: Some(1),
+      keep_days: None,
+      keep_unread: false,
+    };
+    let link = &articles[0].feed_link;
+    assert_eq!(prune(&mut conn, link, &policy).unwrap(), 1);
+
+    // still in the feed, the pruned one is not new again
+    assert!(titles(upsert_articles(&mut conn, articles)).is_empty());
+    let guids = dsl::articles.select(dsl::guid).load::<String>(&mut conn);
+    assert_eq!(guids.unwrap(), vec!["b"]);
+  }
+
+  #[test]
+  fn test_play_queue() {
+    let mut conn = memory_connection();
+    let item = |url: &str| QueueItem {
+      audio_url: url.to_string(),
+      title: url.to_string(),
+      article_url: String::new(),
+      feed_link: String::new(),
+      published: None,
+      queue_order: 0,
+    };
+    let queue = |conn: &mut SqliteConnection| {
+      use schema::play_queue::dsl;
+      dsl::play_queue
+        .order(dsl::queue_order.asc())
+        .select((dsl::audio_url, dsl::queue_order))
+        .load::<(String, i32)>(conn)
+        .unwrap()
+        .into_iter()
+        .map(|(url, order)| format!("{}{}", url, order))
+        .collect::<Vec<String>>()
+        .join(",")
+    };
+
+    enqueue(&mut conn, &item("a"), None).unwrap();
+    enqueue(&mut conn, &item("b"), None).unwrap();
+    enqueue(&mut conn, &item("c"), Some(0)).unwrap();
+    assert_eq!(queue(&mut conn), "c0,a1,b2");
+    // queued already, moved; an index out of range goes to the end
+    enqueue(&mut conn, &item("c"), Some(9)).unwrap();
+    assert_eq!(queue(&mut conn), "a0,b1,c2");
+
+    // the gap is closed
+    assert_eq!(dequeue(&mut conn, "a").unwrap(), 2);
+    assert_eq!(dequeue(&mut conn, "z").unwrap(), 0);
+    assert_eq!(queue(&mut conn), "b0,c1");
+
+    // the ones not listed go after
+    enqueue(&mut conn, &item("d"), None).unwrap();
+    reorder(&mut conn, &["d".to_string(), "c".to_string()]).unwrap();
+    assert_eq!(queue(&mut conn), "d0,c1,b2");
+  }
+
+  #[test]
+  fn test_upsert_articles() {
+    let mut conn = memory_connection();
+    let articles = vec![new_article("a", "A"), new_article("b", "B")];
+    let added = upsert_articles(&mut conn, articles.clone()).unwrap();
+    assert_eq!(added.iter().map(|a| a.id).collect::<Vec<i32>>(), vec![1, 2]);
+    assert_eq!(added[1].title, "B");
+
+    // unchanged, nothing new nor revised
+    assert!(upsert_articles(&mut conn, articles).unwrap().is_empty());
+    assert_eq!(
+      load_articles(&mut conn),
+      vec![("a".into(), "A".into(), 0), ("b".into(), "B".into(), 0)]
+    );
+
+    // media newly parsed, refreshed but not taken as revised
+    let mut media = new_article("b", "B");
+    media.image = "https://mdsilo.com/b.png".to_string();
+    media.duration = Some(60);
+    assert!(upsert_articles(&mut conn, vec![media]).unwrap().is_empty());
+    assert_eq!(load_articles(&mut conn)[1], ("b".into(), "B".into(), 0));
+    let stored = schema::articles::dsl::articles
+      .filter(schema::articles::guid.eq("b"))
+      .select((schema::articles::image, schema::articles::duration))
+      .first::<(String, Option<i32>)>(&mut conn)
+      .unwrap();
+    assert_eq!(stored, ("https://mdsilo.com/b.png".into(), Some(60)));
+
+    // revised by the feed, updated in place
+    let revised = vec![new_article("a", "A revised"), new_article("c", "C")];
+    assert_eq!(titles(upsert_articles(&mut conn, revised)), vec!["C"]);
+    assert_eq!(
+      load_articles(&mut conn),
+      vec![
+        ("a".into(), "A revised".into(), 1),
+        ("b".into(), "B".into(), 0),
+        ("c".into(), "C".into(), 0),
+      ]
+    );
   }
 }
@@ -0,0 +1,120 @@
+// the http client shared by feed requests
+//
+// built once per the persisted settings, rebuilt when they change;
+// the client is cheap to clone, the connection pool is shared
+
+use reqwest::{redirect, Client, Proxy};
+use serde::{Deserialize, Serialize};
+use std::sync::RwLock;
+use std::time::Duration;
+
+use super::FeedError;
+use crate::storage;
+
+// storage key of the http settings
+const HTTP_SETTINGS_KEY: &str = "feed_http";
+
+// sent if no user agent set, some sites reject requests without one
+const DEFAULT_USER_AGENT: &str =
+  concat!("mdSilo/", env!("CARGO_PKG_VERSION"), " (feed reader)");
+
+static CLIENT: RwLock<Option<Client>> = RwLock::new(None);
+
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct HttpSettings {
+  // like `http://127.0.0.1:8080` or `socks5h://127.0.0.1:1080`,
+  // the system proxy on empty
+  pub proxy: String,
+  // in seconds, no limit on None
+  pub connect_timeout: Option<u64>,
+  pub read_timeout: Option<u64>,
+  pub user_agent: String,
+  // 0 to not follow redirects, 10 on None
+  pub max_redirects: Option<usize>,
+}
+
+pub fn load_settings() -> HttpSettings {
+  match storage::get_data(HTTP_SETTINGS_KEY.to_string()) {
+    Ok(data) => serde_json::from_value(data.data).unwrap_or_default(),
+    Err(_) => HttpSettings::default(),
+  }
+}
+
+pub fn build_client(settings: &HttpSettings) -> Result<Client, FeedError> {
+  let user_agent = match settings.user_agent.trim() {
+    "" => DEFAULT_USER_AGENT,
+    ua => ua,
+  };
+  let mut builder = Client::builder().user_agent(user_agent);
+
+  let proxy = settings.proxy.trim();
+  if !proxy.is_empty() {
+    builder = builder.proxy(Proxy::all(proxy)?);
+  }
+  if let Some(secs) = settings.connect_timeout.filter(|s| *s > 0) {
+    builder = builder.connect_timeout(Duration::from_secs(secs));
+  }
+  if let Some(secs) = settings.read_timeout.filter(|s| *s > 0) {
+    builder = builder.read_timeout(Duration::from_secs(secs));
+  }
+  builder = match settings.max_redirects {
+    Some(0) => builder.redirect(redirect::Policy::none()),
+    Some(max) => builder.redirect(redirect::Policy::limited(max)),
+    None => builder,
+  };
+
+  Ok(builder.build()?)
+}
+
+// the shared client, built per the saved settings on first use
+pub fn client() -> Result<Client, FeedError> {
+  if let Some(client) = CLIENT.read().ok().and_then(|c| c.clone()) {
+    return Ok(client);
+  }
+
+  let client = build_client(&load_settings())?;
+  if let Ok(mut shared) = CLIENT.write() {
+    *shared = Some(client.clone());
+  }
+
+  Ok(client)
+}
+
+// check the settings by building a client, then save and use them
+pub fn apply_settings(settings: &HttpSettings) -> Result<(), FeedError> {
+  let client = build_client(settings)?;
+  let value = serde_json::to_value(settings).map_err(|e| FeedError::Io {
+    message: e.to_string(),
+  })?;
+  if !storage::set_data(HTTP_SETTINGS_KEY.to_string(), value) {
+    return Err(FeedError::Io {
+      message: "failed to save http settings".to_string(),
+    });
+  }
+  if let Ok(mut shared) = CLIENT.write() {
+    *shared = Some(client);
+  }
+
+  Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_http_client() {
+    let mut settings = HttpSettings {
+      proxy: "socks5h://127.0.0.1:1080".to_string(),
+      connect_timeout: Some(10),
+      read_timeout: Some(30),
+      user_agent: "Mozilla/5.0".to_string(),
+      max_redirects: Some(0),
+    };
+    assert!(build_client(&settings).is_ok());
+
+    settings.proxy = "not a proxy".to_string();
+    assert!(build_client(&settings).is_err());
+  }
+}
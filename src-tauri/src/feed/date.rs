@@ -0,0 +1,164 @@
+// dates of feeds into utc timestamps
+//
+// RSS should use RFC 822 and Atom RFC 3339, but in the wild there are
+// named time zones, missing or wrong weekdays, no seconds, no time zone
+// (taken as UTC) or a date only
+
+use chrono::{
+  DateTime, FixedOffset, NaiveDate, NaiveDateTime, SubsecRound, TimeZone, Utc,
+};
+
+// named time zones, as the offset
+const TIME_ZONES: [(&str, &str); 24] = [
+  ("UTC", "+0000"),
+  ("GMT", "+0000"),
+  ("WET", "+0000"),
+  ("CET", "+0100"),
+  ("BST", "+0100"),
+  ("CEST", "+0200"),
+  ("EET", "+0200"),
+  ("EEST", "+0300"),
+  ("MSK", "+0300"),
+  ("IST", "+0530"),
+  ("EST", "-0500"),
+  ("EDT", "-0400"),
+  ("CST", "-0600"),
+  ("CDT", "-0500"),
+  ("MST", "-0700"),
+  ("MDT", "-0600"),
+  ("PST", "-0800"),
+  ("PDT", "-0700"),
+  ("HKT", "+0800"),
+  ("SGT", "+0800"),
+  ("JST", "+0900"),
+  ("KST", "+0900"),
+  ("AEST", "+1000"),
+  ("AEDT", "+1100"),
+];
+
+const OFFSET_FORMATS: [&str; 10] = [
+  "%d %b %Y %H:%M:%S %z",
+  "%d %b %Y %H:%M %z",
+  "%d %B %Y %H:%M:%S %z",
+  "%d %b %y %H:%M:%S %z",
+  "%b %d %Y %H:%M:%S %z",
+  "%Y-%m-%dT%H:%M:%S%.f%z",
+  "%Y-%m-%dT%H:%M%z",
+  "%Y-%m-%d %H:%M:%S%.f%z",
+  "%Y-%m-%d %H:%M:%S%.f %z",
+  "%Y-%m-%d %H:%M%z",
+];
+
+// no time zone, taken as utc
+const NAIVE_FORMATS: [&str; 8] = [
+  "%Y-%m-%dT%H:%M:%S%.f",
+  "%Y-%m-%d %H:%M:%S%.f",
+  "%Y-%m-%dT%H:%M",
+  "%Y-%m-%d %H:%M",
+  "%Y/%m/%d %H:%M:%S",
+  "%d %b %Y %H:%M:%S",
+  "%d %b %Y %H:%M",
+  "%b %d %Y %H:%M:%S",
+];
+
+const DATE_FORMATS: [&str; 7] = [
+  "%Y-%m-%d", "%Y/%m/%d", "%Y%m%d", "%d %b %Y", "%d %B %Y", "%b %d %Y", "%B %d %Y",
+];
+
+// the time an article without date is first seen
+pub fn now() -> DateTime<Utc> {
+  Utc::now().trunc_subsecs(0)
+}
+
+pub fn to_utc<Tz: TimeZone>(date: DateTime<Tz>) -> DateTime<Utc> {
+  date.with_timezone(&Utc).trunc_subsecs(0)
+}
+
+pub fn parse_date(date: &str) -> Option<DateTime<Utc>> {
+  let date = date.trim();
+  if date.is_empty() {
+    return None;
+  }
+
+  DateTime::parse_from_rfc2822(date)
+    .or_else(|_| DateTime::parse_from_rfc3339(date))
+    .ok()
+    .or_else(|| parse_loose(date))
+    .map(to_utc)
+}
+
+fn parse_loose(date: &str) -> Option<DateTime<FixedOffset>> {
+  // the weekday is redundant, and sometimes wrong
+  let date = match date.split_once(',') {
+    Some((day, rest)) if day.chars().all(|c| c.is_ascii_alphabetic()) => rest,
+    _ => date,
+  };
+  // like `March 6, 2024`
+  let date = date.replace(',', " ");
+  let mut words: Vec<&str> = date.split_whitespace().collect();
+  let zone = words
+    .last()
+    .and_then(|w| {
+      TIME_ZONES
+        .iter()
+        .find(|(name, _)| w.eq_ignore_ascii_case(name))
+    })
+    .map(|(_, offset)| *offset);
+  if let Some(offset) = zone {
+    words.pop();
+    words.push(offset);
+  }
+  let date = words.join(" ");
+  let date = match date.strip_suffix(['Z', 'z']) {
+    Some(d) if d.ends_with(|c: char| c.is_ascii_digit()) => format!("{}+0000", d),
+    _ => date,
+  };
+
+  for format in OFFSET_FORMATS {
+    if let Ok(dt) = DateTime::parse_from_str(&date, format) {
+      return Some(dt);
+    }
+  }
+  for format in NAIVE_FORMATS {
+    if let Ok(dt) = NaiveDateTime::parse_from_str(&date, format) {
+      return Some(Utc.from_utc_datetime(&dt).fixed_offset());
+    }
+  }
+  for format in DATE_FORMATS {
+    if let Ok(d) = NaiveDate::parse_from_str(&date, format) {
+      return Some(
+        Utc
+          .from_utc_datetime(&d.and_hms_opt(0, 0, 0)?)
+          .fixed_offset(),
+      );
+    }
+  }
+
+  None
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_parse_date() {
+    let expected = Utc.with_ymd_and_hms(2024, 3, 6, 0, 0, 0).single();
+    for d in [
+      "Wed, 06 Mar 2024 08:00:00 +0800",
+      "Mon, 6 Mar 2024 00:00:00 GMT",
+      "06 Mar 2024 01:00:00 CET",
+      "Wed, 06 Mar 2024 00:00 UTC",
+      "2024-03-06T08:00:00.123+08:00",
+      "2024-03-06T08:00:00+0800",
+      "2024-03-06 00:00:00Z",
+      "2024-03-06 00:00:00",
+      "2024-03-06",
+      "Wednesday, March 6, 2024",
+    ] {
+      assert_eq!(parse_date(d), expected, "{}", d);
+    }
+    assert_eq!(parse_date(""), None);
+    assert_eq!(parse_date("yesterday"), None);
+  }
+}
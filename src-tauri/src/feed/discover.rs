@@ -0,0 +1,167 @@
+// feed autodiscovery from a website
+//
+// - <link rel="alternate" type="application/rss+xml" href="..."> in html
+// - or guess on the common feed paths of the site
+
+use bytes::Bytes;
+use futures::future::join_all;
+use reqwest::Url;
+use scraper::{Html, Selector};
+use serde::Serialize;
+
+use super::{detect_format, request_content, FeedContent, FeedFormat};
+
+// probed all at once, the valid feeds are offered in this order
+const COMMON_PATHS: [&str; 8] = [
+  "/feed",
+  "/rss.xml",
+  "/feed.xml",
+  "/atom.xml",
+  "/index.xml",
+  "/rss",
+  "/feed.json",
+  "/feed/",
+];
+
+#[derive(Debug, Clone, Serialize)]
+pub struct FeedCandidate {
+  pub url: String,
+  pub title: String,
+  pub format: FeedFormat,
+}
+
+pub fn is_html(content_type: &str, content: &[u8]) -> bool {
+  if content_type.to_lowercase().contains("html") {
+    return true;
+  }
+  let head = &content[..content.len().min(512)];
+  let head = String::from_utf8_lossy(head).trim_start().to_lowercase();
+
+  head.starts_with("<!doctype html") || head.starts_with("<html")
+}
+
+// find feeds in the html, in document order
+pub fn find_feed_links(html: &str, page_url: &str) -> Vec<FeedCandidate> {
+  let document = Html::parse_document(html);
+  let base = match Url::parse(page_url) {
+    Ok(url) => url,
+    Err(_) => return vec![],
+  };
+  // respect <base href="..."> if any
+  let base = Selector::parse("base[href]")
+    .ok()
+    .and_then(|sel| document.select(&sel).next())
+    .and_then(|el| el.value().attr("href"))
+    .and_then(|href| base.join(href).ok())
+    .unwrap_or(base);
+
+  let selector = match Selector::parse("link[rel][type][href]") {
+    Ok(sel) => sel,
+    Err(_) => return vec![],
+  };
+
+  let mut candidates: Vec<FeedCandidate> = vec![];
+  for el in document.select(&selector) {
+    let attrs = el.value();
+    let is_alternate = attrs
+      .attr("rel")
+      .unwrap_or_default()
+      .split_ascii_whitespace()
+      .any(|rel| rel.eq_ignore_ascii_case("alternate"));
+    if !is_alternate {
+      continue;
+    }
+    let ty = attrs.attr("type").unwrap_or_default().trim().to_lowercase();
+    let format = match ty.as_str() {
+      "application/rss+xml" | "application/rdf+xml" => FeedFormat::Rss,
+      "application/atom+xml" => FeedFormat::Atom,
+      "application/feed+json" | "application/json" => FeedFormat::Json,
+      _ => continue,
+    };
+    let url = match base.join(attrs.attr("href").unwrap_or_default().trim()) {
+      Ok(url) => url.to_string(),
+      Err(_) => continue,
+    };
+    if candidates.iter().any(|c| c.url == url) {
+      continue;
+    }
+
+    candidates.push(FeedCandidate {
+      url,
+      title: attrs.attr("title").unwrap_or_default().trim().to_string(),
+      format,
+    });
+  }
+
+  candidates
+}
+
+// probe the common feed paths of the site, get all the feeds found;
+// a feed served on several paths, like `/feed` and `/feed/`, once
+pub async fn probe_common_paths(page_url: &str) -> Vec<FeedCandidate> {
+  let base = match Url::parse(page_url) {
+    Ok(url) => url,
+    Err(_) => return vec![],
+  };
+  let urls: Vec<String> = COMMON_PATHS
+    .iter()
+    .filter_map(|path| base.join(path).ok())
+    .map(|url| url.to_string())
+    .collect();
+
+  // the missed paths are expected, not logged
+  let probes = urls.into_iter().map(|url| async move {
+    match request_content(&url, None, None).await {
+      Ok(FeedContent::Modified {
+        content,
+        content_type,
+        ..
+      }) => detect_format(&content_type, &content).map(|format| {
+        let candidate = FeedCandidate {
+          url,
+          title: String::new(),
+          format,
+        };
+        (candidate, content)
+      }),
+      _ => None,
+    }
+  });
+
+  let mut found: Vec<(FeedCandidate, Bytes)> = vec![];
+  for (candidate, content) in join_all(probes).await.into_iter().flatten() {
+    if !found.iter().any(|(_, seen)| *seen == content) {
+      found.push((candidate, content));
+    }
+  }
+
+  found.into_iter().map(|(candidate, _)| candidate).collect()
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_find_feed_links() {
+    let html = r#"<!DOCTYPE html><html><head>
+      <link rel="stylesheet" type="text/css" href="/style.css">
+      <link rel="alternate" type="application/rss+xml" title="RSS" href="/rss.xml">
+      <link rel="Alternate" type="application/atom+xml" href="https://mdsilo.com/atom.xml">
+      <link rel="alternate" type="application/feed+json" title="JSON" href="feed.json">
+    </head><body></body></html>"#;
+    assert!(is_html("text/html; charset=utf-8", html.as_bytes()));
+    let candidates = find_feed_links(html, "https://mdsilo.com/blog/");
+    let urls: Vec<&str> = candidates.iter().map(|c| c.url.as_str()).collect();
+    assert_eq!(
+      urls,
+      vec![
+        "https://mdsilo.com/rss.xml",
+        "https://mdsilo.com/atom.xml",
+        "https://mdsilo.com/blog/feed.json",
+      ]
+    );
+    assert_eq!(candidates[0].title, "RSS");
+    assert_eq!(candidates[2].format, FeedFormat::Json);
+  }
+}
@@ -0,0 +1,206 @@
+// podcast episodes downloaded for offline play
+//
+// saved as `episodes/<article id>.<ext>` in the data dir, the cache is
+// kept under a size limit by deleting the played episodes
+
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use tokio::io::AsyncWriteExt;
+
+use super::{client, FeedError};
+use crate::storage::create_mdsilo_dir;
+
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct EpisodeCacheSettings {
+  // in MB, no limit on None
+  pub max_size_mb: Option<u64>,
+  // delete an episode once played
+  pub delete_played: bool,
+}
+
+impl EpisodeCacheSettings {
+  pub fn max_bytes(&self) -> Option<u64> {
+    self.max_size_mb.map(|mb| mb * 1024 * 1024)
+  }
+}
+
+impl From<std::io::Error> for FeedError {
+  fn from(e: std::io::Error) -> Self {
+    FeedError::Io {
+      message: e.to_string(),
+    }
+  }
+}
+
+// ids of the episodes downloading, till their path is saved
+static DOWNLOADING: Mutex<Vec<i32>> = Mutex::new(Vec::new());
+
+// one download per episode, done on drop
+pub struct Downloading(i32);
+
+impl Downloading {
+  // None if the episode is downloading already
+  pub fn start(article_id: i32) -> Option<Downloading> {
+    let mut downloading = DOWNLOADING.lock().ok()?;
+    if downloading.contains(&article_id) {
+      return None;
+    }
+    downloading.push(article_id);
+
+    Some(Downloading(article_id))
+  }
+}
+
+impl Drop for Downloading {
+  fn drop(&mut self) {
+    if let Ok(mut downloading) = DOWNLOADING.lock() {
+      downloading.retain(|id| *id != self.0);
+    }
+  }
+}
+
+// delete the files neither saved nor downloading, like of deleted
+// articles or failed downloads; the saved paths are read once locked,
+// the files deleted are claimed as downloading, no download of them
+// starts meanwhile
+pub async fn remove_unsaved(
+  dir: &Path,
+  saved_paths: impl FnOnce() -> Vec<String>,
+) -> usize {
+  let paths = read_paths(dir).await;
+  let claimed: Vec<(PathBuf, Option<Downloading>)> = {
+    let mut downloading = match DOWNLOADING.lock() {
+      Ok(downloading) => downloading,
+      Err(_) => return 0,
+    };
+    let in_flight = downloading.clone();
+    let saved = saved_paths();
+    paths
+      .into_iter()
+      .filter(|path| !saved.iter().any(|saved| Path::new(saved) == path))
+      .filter_map(|path| {
+        // `<article id>.<ext>` or `<article id>.part`
+        let article_id = path
+          .file_stem()
+          .and_then(|stem| stem.to_str())
+          .and_then(|stem| stem.parse::<i32>().ok());
+        match article_id {
+          Some(id) if in_flight.contains(&id) => None,
+          // claimed for another file of it, like the `.part`
+          Some(id) if downloading.contains(&id) => Some((path, None)),
+          Some(id) => {
+            downloading.push(id);
+            Some((path, Some(Downloading(id))))
+          }
+          None => Some((path, None)),
+        }
+      })
+      .collect()
+  };
+
+  let mut removed = 0;
+  for (path, _claimed) in claimed.iter() {
+    if tokio::fs::remove_file(path).await.is_ok() {
+      removed += 1;
+    }
+  }
+
+  removed
+}
+
+async fn read_paths(dir: &Path) -> Vec<PathBuf> {
+  let mut paths = vec![];
+  if let Ok(mut entries) = tokio::fs::read_dir(dir).await {
+    while let Ok(Some(entry)) = entries.next_entry().await {
+      paths.push(entry.path());
+    }
+  }
+
+  paths
+}
+
+pub async fn episodes_dir() -> Result<PathBuf, FeedError> {
+  let data_dir = create_mdsilo_dir().ok_or(FeedError::Io {
+    message: "no data dir".to_string(),
+  })?;
+  let dir = data_dir.join("episodes");
+  tokio::fs::create_dir_all(&dir).await?;
+
+  Ok(dir)
+}
+
+// keep the extension of the audio url, like `.mp3` or `.m4a`
+pub fn episode_path(dir: &Path, article_id: i32, audio_url: &str) -> PathBuf {
+  let ext = reqwest::Url::parse(audio_url)
+    .ok()
+    .and_then(|url| {
+      Path::new(url.path())
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .map(str::to_lowercase)
+    })
+    .filter(|ext| ext.len() <= 5 && ext.chars().all(|c| c.is_ascii_alphanumeric()))
+    .unwrap_or_else(|| "mp3".to_string());
+
+  dir.join(format!("{}.{}", article_id, ext))
+}
+
+// stream the url into the path, via a `.part` file;
+// get the number of bytes, hold a `Downloading` of the episode meanwhile
+pub async fn download(
+  url: &str,
+  path: &Path,
+  mut on_progress: impl FnMut(u64, Option<u64>),
+) -> Result<u64, FeedError> {
+  let mut response = client::client()?.get(url).send().await?;
+  if !response.status().is_success() {
+    return Err(FeedError::Status {
+      code: response.status().as_u16(),
+    });
+  }
+
+  let total = response.content_length();
+  let part = path.with_extension("part");
+  let mut file = tokio::fs::File::create(&part).await?;
+  let mut downloaded: u64 = 0;
+  let result = async {
+    while let Some(chunk) = response.chunk().await? {
+      file.write_all(&chunk).await?;
+      downloaded += chunk.len() as u64;
+      on_progress(downloaded, total);
+    }
+    file.flush().await?;
+    Ok::<(), FeedError>(())
+  }
+  .await;
+
+  match result {
+    Ok(_) => {
+      tokio::fs::rename(&part, path).await?;
+      Ok(downloaded)
+    }
+    Err(e) => {
+      tokio::fs::remove_file(&part).await.unwrap_or(());
+      Err(e)
+    }
+  }
+}
+
+pub async fn file_size(path: &Path) -> u64 {
+  tokio::fs::metadata(path)
+    .await
+    .map(|m| m.len())
+    .unwrap_or(0)
+}
+
+// size of the downloaded episodes, in bytes
+pub async fn cache_size(dir: &Path) -> u64 {
+  let mut size = 0;
+  for path in read_paths(dir).await {
+    size += file_size(&path).await;
+  }
+
+  size
+}
@@ -0,0 +1,214 @@
+// filter rules, matching the articles on ingest or re-applied later
+//
+// keyword and regex look into the title, description and content;
+// author and category into their own fields; all case insensitive
+
+use regex::{Regex, RegexBuilder};
+use serde::Serialize;
+
+use crate::models::{Article, FilterRule, NewArticle, NewFilterRule};
+
+// the fields of an article to match
+pub struct Item<'a> {
+  pub feed_link: &'a str,
+  pub title: &'a str,
+  pub description: &'a str,
+  pub content: &'a str,
+  pub author: &'a str,
+  // comma separated
+  pub categories: &'a str,
+}
+
+impl<'a> From<&'a NewArticle> for Item<'a> {
+  fn from(article: &'a NewArticle) -> Self {
+    Item {
+      feed_link: &article.feed_link,
+      title: &article.title,
+      description: &article.description,
+      content: &article.content,
+      author: &article.author,
+      categories: &article.categories,
+    }
+  }
+}
+
+impl<'a> From<&'a Article> for Item<'a> {
+  fn from(article: &'a Article) -> Self {
+    Item {
+      feed_link: &article.feed_link,
+      title: &article.title,
+      description: &article.description,
+      content: &article.content,
+      author: &article.author,
+      categories: &article.categories,
+    }
+  }
+}
+
+enum Matcher {
+  // lowercased
+  Keyword(String),
+  Regex(Regex),
+  Author(String),
+  Category(String),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Action {
+  Read,
+  Star,
+  Hide,
+  Delete,
+}
+
+pub struct Rule {
+  channel_link: Option<String>,
+  matcher: Matcher,
+  action: Action,
+}
+
+impl Rule {
+  pub fn new(rule: &NewFilterRule) -> Result<Rule, String> {
+    let pattern = rule.pattern.trim();
+    if pattern.is_empty() {
+      return Err("empty pattern".to_string());
+    }
+    let matcher = match rule.kind.as_str() {
+      "keyword" => Matcher::Keyword(pattern.to_lowercase()),
+      "regex" => Matcher::Regex(
+        RegexBuilder::new(pattern)
+          .case_insensitive(true)
+          .build()
+          .map_err(|e| e.to_string())?,
+      ),
+      "author" => Matcher::Author(pattern.to_lowercase()),
+      "category" => Matcher::Category(pattern.to_lowercase()),
+      kind => return Err(format!("unknown rule kind: {}", kind)),
+    };
+    let action = match rule.action.as_str() {
+      "read" => Action::Read,
+      "star" => Action::Star,
+      "hide" => Action::Hide,
+      "delete" => Action::Delete,
+      action => return Err(format!("unknown rule action: {}", action)),
+    };
+
+    Ok(Rule {
+      channel_link: rule.channel_link.clone().filter(|l| !l.is_empty()),
+      matcher,
+      action,
+    })
+  }
+
+  pub fn matches(&self, item: &Item) -> bool {
+    if let Some(link) = &self.channel_link {
+      if link != item.feed_link {
+        return false;
+      }
+    }
+
+    let texts = [item.title, item.description, item.content];
+    match &self.matcher {
+      Matcher::Keyword(keyword) => texts
+        .iter()
+        .any(|text| text.to_lowercase().contains(keyword)),
+      Matcher::Regex(regex) => texts.iter().any(|text| regex.is_match(text)),
+      Matcher::Author(author) => item.author.to_lowercase().contains(author),
+      Matcher::Category(category) => item
+        .categories
+        .split(',')
+        .any(|c| c.trim().to_lowercase() == *category),
+    }
+  }
+}
+
+// actions of the rules an article matches
+#[derive(Debug, Default, Clone, Copy, PartialEq, Serialize)]
+pub struct Actions {
+  pub read: bool,
+  pub star: bool,
+  pub hide: bool,
+  pub delete: bool,
+}
+
+impl Actions {
+  pub fn any(&self) -> bool {
+    self.read || self.star || self.hide || self.delete
+  }
+}
+
+pub struct RuleSet(Vec<Rule>);
+
+impl RuleSet {
+  // the invalid ones are left out, checked on creating anyway
+  pub fn new(rules: Vec<FilterRule>) -> RuleSet {
+    RuleSet(
+      rules
+        .into_iter()
+        .filter_map(|r| {
+          Rule::new(&NewFilterRule {
+            channel_link: r.channel_link,
+            kind: r.kind,
+            pattern: r.pattern,
+            action: r.action,
+          })
+          .ok()
+        })
+        .collect(),
+    )
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.0.is_empty()
+  }
+
+  pub fn actions(&self, item: &Item) -> Actions {
+    let mut actions = Actions::default();
+    for rule in self.0.iter().filter(|rule| rule.matches(item)) {
+      match rule.action {
+        Action::Read => actions.read = true,
+        Action::Star => actions.star = true,
+        Action::Hide => actions.hide = true,
+        Action::Delete => actions.delete = true,
+      }
+    }
+
+    actions
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_filter_rules() {
+    let rule = |channel_link: Option<&str>, kind: &str, pattern: &str| {
+      Rule::new(&NewFilterRule {
+        channel_link: channel_link.map(String::from),
+        kind: kind.to_string(),
+        pattern: pattern.to_string(),
+        action: "hide".to_string(),
+      })
+    };
+    let item = Item {
+      feed_link: "https://mdsilo.com/rss.xml",
+      title: "Sponsored: Rust 2024",
+      description: "",
+      content: "<p>the new edition</p>",
+      author: "Alice, Bob",
+      categories: "Rust, Release",
+    };
+
+    assert!(rule(None, "keyword", "sponsored").unwrap().matches(&item));
+    assert!(rule(None, "keyword", "EDITION").unwrap().matches(&item));
+    assert!(rule(None, "regex", r"^sponsored:").unwrap().matches(&item));
+    assert!(rule(None, "author", "bob").unwrap().matches(&item));
+    assert!(rule(None, "category", "release").unwrap().matches(&item));
+    assert!(!rule(None, "category", "rel").unwrap().matches(&item));
+    let other = Some("https://mdsilo.com/other.xml");
+    assert!(!rule(other, "keyword", "rust").unwrap().matches(&item));
+    assert!(rule(None, "regex", "(").is_err());
+    assert!(rule(None, "title", "rust").is_err());
+  }
+}
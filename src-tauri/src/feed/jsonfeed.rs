@@ -0,0 +1,109 @@
+// JSON Feed, version 1.1 and 1.0
+// spec: https://www.jsonfeed.org/version/1.1/
+//
+// only the fields mapped onto channel and articles are read
+
+use serde::{Deserialize, Deserializer};
+use serde_json::Value;
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+pub struct JsonFeed {
+  pub title: String,
+  pub description: Option<String>,
+  pub icon: Option<String>,
+  pub items: Vec<Item>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+pub struct Item {
+  // a string, but some feeds use a number
+  #[serde(deserialize_with = "string_or_number")]
+  pub id: Option<String>,
+  pub url: Option<String>,
+  pub external_url: Option<String>,
+  pub title: Option<String>,
+  pub content_html: Option<String>,
+  pub content_text: Option<String>,
+  pub summary: Option<String>,
+  pub image: Option<String>,
+  pub banner_image: Option<String>,
+  pub date_published: Option<String>,
+  pub date_modified: Option<String>,
+  pub tags: Vec<String>,
+  pub authors: Vec<Author>,
+  // deprecated in 1.1, still used by 1.0 feeds
+  pub author: Option<Author>,
+  pub attachments: Vec<Attachment>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+pub struct Author {
+  pub name: Option<String>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+pub struct Attachment {
+  pub url: String,
+  pub mime_type: String,
+  pub duration_in_seconds: Option<f64>,
+}
+
+impl JsonFeed {
+  pub fn read_from(content: &[u8]) -> Result<JsonFeed, serde_json::Error> {
+    serde_json::from_slice(content)
+  }
+}
+
+impl Item {
+  // names of all authors, joined
+  pub fn author_names(&self) -> String {
+    self
+      .authors
+      .iter()
+      .chain(self.author.iter())
+      .filter_map(|a| a.name.clone())
+      .collect::<Vec<String>>()
+      .join(", ")
+  }
+}
+
+fn string_or_number<'de, D>(deserializer: D) -> Result<Option<String>, D::Error>
+where
+  D: Deserializer<'de>,
+{
+  Ok(match Value::deserialize(deserializer)? {
+    Value::String(s) => Some(s),
+    Value::Number(n) => Some(n.to_string()),
+    _ => None,
+  })
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_read_json_feed() {
+    let json = r#"{
+      "version": "https://jsonfeed.org/version/1",
+      "title": "mdSilo",
+      "items": [
+        { "id": 1, "authors": [{ "name": "dloh" }], "author": { "name": "mdSilo" } },
+        { "id": "2", "tags": ["rust"] },
+        { "id": null }
+      ]
+    }"#;
+    let feed = JsonFeed::read_from(json.as_bytes()).unwrap();
+    assert_eq!(feed.title, "mdSilo");
+    assert_eq!(feed.items[0].id.as_deref(), Some("1"));
+    assert_eq!(feed.items[0].author_names(), "dloh, mdSilo");
+    assert_eq!(feed.items[1].id.as_deref(), Some("2"));
+    assert_eq!(feed.items[1].tags, vec!["rust"]);
+    assert_eq!(feed.items[2].id, None);
+    assert!(JsonFeed::read_from(b"<rss/>").is_err());
+  }
+}
@@ -0,0 +1,119 @@
+// media of an item: Media RSS elements and images in the content
+// spec: https://www.rssboard.org/media-rss
+//
+// both rss and atom crates have their own Extension, of the same shape
+
+use reqwest::Url;
+use scraper::{Html, Selector};
+use std::collections::BTreeMap;
+
+pub trait MediaElement: Sized {
+  fn attr(&self, name: &str) -> Option<&str>;
+  fn value(&self) -> Option<&str>;
+  fn children(&self) -> &BTreeMap<String, Vec<Self>>;
+}
+
+impl MediaElement for rss::extension::Extension {
+  fn attr(&self, name: &str) -> Option<&str> {
+    self.attrs.get(name).map(String::as_str)
+  }
+
+  fn value(&self) -> Option<&str> {
+    self.value.as_deref()
+  }
+
+  fn children(&self) -> &BTreeMap<String, Vec<Self>> {
+    &self.children
+  }
+}
+
+impl MediaElement for atom_syndication::extension::Extension {
+  fn attr(&self, name: &str) -> Option<&str> {
+    self.attrs.get(name).map(String::as_str)
+  }
+
+  fn value(&self) -> Option<&str> {
+    self.value.as_deref()
+  }
+
+  fn children(&self) -> &BTreeMap<String, Vec<Self>> {
+    &self.children
+  }
+}
+
+// url of media:content of the medium, `image`, `audio` or `video`,
+// the media:thumbnail for image; also looks into media:group
+pub fn media_url<E: MediaElement>(
+  elements: Option<&BTreeMap<String, Vec<E>>>,
+  medium: &str,
+) -> Option<String> {
+  let elements = elements?;
+  let get = |name: &str| elements.get(name).into_iter().flatten();
+
+  let thumbnail = get("thumbnail")
+    .filter(|_| medium == "image")
+    .find_map(|e| e.attr("url"));
+  let content = get("content")
+    .filter(|e| {
+      e.attr("medium") == Some(medium)
+        || e
+          .attr("type")
+          .map(|t| t.starts_with(&format!("{}/", medium)))
+          .unwrap_or(false)
+    })
+    .find_map(|e| e.attr("url"));
+
+  thumbnail
+    .or(content)
+    .map(String::from)
+    .or_else(|| get("group").find_map(|g| media_url(Some(g.children()), medium)))
+}
+
+// text of a media element like media:description, also in media:group
+pub fn media_text<E: MediaElement>(
+  elements: Option<&BTreeMap<String, Vec<E>>>,
+  name: &str,
+) -> Option<String> {
+  let elements = elements?;
+  elements
+    .get(name)
+    .into_iter()
+    .flatten()
+    .find_map(|e| e.value())
+    .map(String::from)
+    .or_else(|| {
+      elements
+        .get("group")
+        .into_iter()
+        .flatten()
+        .find_map(|g| media_text(Some(g.children()), name))
+    })
+}
+
+// src of the first <img> in html, absolute per the item url
+pub fn first_image(html: &str, base: &str) -> Option<String> {
+  let selector = Selector::parse("img[src]").ok()?;
+  let fragment = Html::parse_fragment(html);
+  let src = fragment
+    .select(&selector)
+    .filter_map(|el| el.value().attr("src"))
+    .map(str::trim)
+    .find(|src| !src.is_empty() && !src.starts_with("data:"))?;
+
+  match Url::parse(base) {
+    Ok(base) => base.join(src).ok().map(|url| url.to_string()),
+    Err(_) => Url::parse(src).ok().map(|url| url.to_string()),
+  }
+}
+
+// non-empty ones, deduplicated, joined
+pub fn join_names<'a>(names: impl Iterator<Item = &'a str>) -> String {
+  let mut joined: Vec<&str> = vec![];
+  for name in names.map(str::trim) {
+    if !name.is_empty() && !joined.contains(&name) {
+      joined.push(name);
+    }
+  }
+
+  joined.join(", ")
+}
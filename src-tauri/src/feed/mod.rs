@@ -0,0 +1,1851 @@
+use bytes::Bytes;
+use chrono::offset::Local;
+use chrono::{DateTime, Utc};
+use futures::stream::{self, StreamExt};
+use reqwest;
+use reqwest::header::{
+  HeaderMap, HeaderName, CONTENT_TYPE, ETAG, IF_MODIFIED_SINCE, IF_NONE_MATCH,
+  LAST_MODIFIED,
+};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use tauri::{command, Manager};
+
+use crate::db;
+use crate::models::{
+  Article, Channel, ChannelAuth, FilterRule, Folder, NewArticle, NewChannel,
+  NewFilterRule, Playback, QueueItem, RetentionPolicy,
+};
+use crate::storage::{self, do_log};
+use crate::tray;
+
+use self::discover::FeedCandidate;
+use self::jsonfeed::JsonFeed;
+
+pub mod client;
+pub mod date;
+pub mod discover;
+pub mod download;
+pub mod filter;
+pub mod jsonfeed;
+pub mod media;
+pub mod notify;
+pub mod opml;
+pub mod podcast;
+pub mod readability;
+pub mod schedule;
+pub mod secret;
+
+// # process rss, atom or json feed #
+//
+// process: rss or podcast typed
+//
+// the url can be a website, then look for its feeds:
+// subscribe if only one found, otherwise return the candidates
+pub async fn process_feed(
+  url: &str,
+  ty: &str,
+  title: Option<String>,
+  auth: Option<&ChannelAuth>,
+) -> Result<(NewChannel, Vec<NewArticle>), FeedError> {
+  let (content, content_type, cache) = fetch_feed_content(url, auth).await?;
+  if detect_format(&content_type, &content).is_some()
+    || !discover::is_html(&content_type, &content)
+  {
+    return parse_fetched(&content, &content_type, cache, url, ty, title);
+  }
+
+  let mut candidates =
+    discover::find_feed_links(&String::from_utf8_lossy(&content), url);
+  if candidates.is_empty() {
+    candidates.extend(discover::probe_common_paths(url).await);
+  }
+
+  match candidates.len() {
+    0 => Err(FeedError::UnknownFormat { content_type }),
+    1 => {
+      let feed_url = candidates.remove(0).url;
+      let (content, content_type, cache) =
+        fetch_feed_content(&feed_url, auth).await?;
+      parse_fetched(&content, &content_type, cache, &feed_url, ty, title)
+    }
+    _ => Err(FeedError::MultipleFeeds { candidates }),
+  }
+}
+
+async fn fetch_feed_content(
+  url: &str,
+  auth: Option<&ChannelAuth>,
+) -> Result<(Bytes, String, HttpCache), FeedError> {
+  match get_feed_content(url, None, auth).await? {
+    FeedContent::Modified {
+      content,
+      content_type,
+      cache,
+    } => Ok((content, content_type, cache)),
+    // no validators sent, should not happen
+    FeedContent::NotModified => Err(FeedError::Status { code: 304 }),
+  }
+}
+
+fn parse_fetched(
+  content: &[u8],
+  content_type: &str,
+  cache: HttpCache,
+  url: &str,
+  ty: &str,
+  title: Option<String>,
+) -> Result<(NewChannel, Vec<NewArticle>), FeedError> {
+  let (mut channel, articles) = parse_feed(content, content_type, url, ty, title)?;
+  channel.etag = cache.etag;
+  channel.last_modified = cache.last_modified;
+
+  Ok((channel, articles))
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "kind")]
+pub enum FeedError {
+  // failed to send the request or read the response
+  Network { message: String },
+  // response status other than 200 or 304
+  Status { code: u16 },
+  // 401 or 403, the feed needs credentials or rejects the given ones
+  Unauthorized { code: u16 },
+  // content is not a rss, atom or json feed
+  UnknownFormat { content_type: String },
+  Parse { format: FeedFormat, message: String },
+  // more than one feed found on a website, choose one to subscribe
+  MultipleFeeds { candidates: Vec<FeedCandidate> },
+  // failed to save a download
+  Io { message: String },
+  // no main content found on the article page
+  NoContent { url: String },
+  // credentials other than `basic` or `bearer`
+  UnknownAuth { auth_kind: String },
+  // failed to save the fetched articles
+  Db { message: String },
+}
+
+impl From<reqwest::Error> for FeedError {
+  fn from(e: reqwest::Error) -> Self {
+    FeedError::Network {
+      message: e.to_string(),
+    }
+  }
+}
+
+impl std::fmt::Display for FeedError {
+  fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+    match self {
+      FeedError::Network { message } => write!(f, "network error: {}", message),
+      FeedError::Status { code } => write!(f, "response status: {}", code),
+      FeedError::Unauthorized { code: 401 } => {
+        write!(f, "authentication required (401), check the credentials")
+      }
+      FeedError::Unauthorized { code } => {
+        write!(f, "access denied ({}), check the credentials", code)
+      }
+      FeedError::UnknownFormat { content_type } => {
+        write!(f, "not a feed, content type: {}", content_type)
+      }
+      FeedError::Parse { format, message } => {
+        write!(f, "failed to parse {:?} feed: {}", format, message)
+      }
+      FeedError::MultipleFeeds { candidates } => {
+        write!(f, "found {} feeds, choose one", candidates.len())
+      }
+      FeedError::Io { message } => write!(f, "file error: {}", message),
+      FeedError::NoContent { url } => {
+        write!(f, "no article content found on {}", url)
+      }
+      FeedError::UnknownAuth { auth_kind } => {
+        write!(f, "unknown auth kind: {}", auth_kind)
+      }
+      FeedError::Db { message } => write!(f, "database error: {}", message),
+    }
+  }
+}
+
+// parse the fetched content per the detected format
+pub fn parse_feed(
+  content: &[u8],
+  content_type: &str,
+  url: &str,
+  ty: &str,
+  title: Option<String>,
+) -> Result<(NewChannel, Vec<NewArticle>), FeedError> {
+  let result = match detect_format(content_type, content) {
+    Some(FeedFormat::Rss) => process_rss(content, url, ty, title),
+    Some(FeedFormat::Atom) => process_atom(content, url, ty, title),
+    Some(FeedFormat::Json) => process_json(content, url, ty, title),
+    None => Err(FeedError::UnknownFormat {
+      content_type: content_type.to_string(),
+    }),
+  };
+
+  result.map_err(|e| {
+    do_log(
+      "Error".to_string(),
+      format!("Err on [parse_feed: {}]: {}", url, e),
+      format!("{}", Local::now().format("%m/%d/%Y %H:%M:%S")),
+    );
+    e
+  })
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub enum FeedFormat {
+  Rss,
+  Atom,
+  Json,
+}
+
+// sniff the content first, servers often send a generic or wrong type,
+// then fall back on the content type
+pub fn detect_format(content_type: &str, content: &[u8]) -> Option<FeedFormat> {
+  sniff_format(content).or_else(|| {
+    let mime = content_type.split(';').next().unwrap_or("").trim();
+    match mime.to_lowercase().as_str() {
+      "application/rss+xml" | "application/rdf+xml" => Some(FeedFormat::Rss),
+      "application/atom+xml" => Some(FeedFormat::Atom),
+      "application/feed+json" | "application/json" => Some(FeedFormat::Json),
+      _ => None,
+    }
+  })
+}
+
+fn sniff_format(content: &[u8]) -> Option<FeedFormat> {
+  let content = content.strip_prefix(b"\xEF\xBB\xBF").unwrap_or(content);
+  let start = content.iter().position(|b| !b.is_ascii_whitespace())?;
+  if content[start] == b'{' {
+    return Some(FeedFormat::Json);
+  }
+
+  // the root element, after xml declaration, comments or stylesheet
+  let mut rest = &content[start..];
+  while let Some(pos) = rest.iter().position(|b| *b == b'<') {
+    rest = &rest[pos..];
+    let end = if rest.starts_with(b"<!--") {
+      find_bytes(rest, b"-->").map(|i| i + 3)
+    } else if rest.starts_with(b"<?") || rest.starts_with(b"<!") {
+      find_bytes(rest, b">").map(|i| i + 1)
+    } else {
+      let name_end = rest
+        .iter()
+        .position(|b| b.is_ascii_whitespace() || *b == b'>' || *b == b'/')
+        .unwrap_or(rest.len());
+      return match &rest[1..name_end] {
+        b"rss" | b"rdf:RDF" => Some(FeedFormat::Rss),
+        b"feed" => Some(FeedFormat::Atom),
+        _ => None,
+      };
+    };
+    rest = &rest[end?..];
+  }
+
+  None
+}
+
+fn find_bytes(haystack: &[u8], needle: &[u8]) -> Option<usize> {
+  haystack.windows(needle.len()).position(|w| w == needle)
+}
+
+// cache validators from the last response of a feed
+#[derive(Debug, Clone, Default)]
+pub struct HttpCache {
+  pub etag: String,
+  pub last_modified: String,
+}
+
+pub enum FeedContent {
+  Modified {
+    content: Bytes,
+    content_type: String,
+    cache: HttpCache,
+  },
+  // 304, nothing new since the cached response
+  NotModified,
+}
+
+// 0- get content bytes
+//
+// with cache validators, send a conditional request;
+// with credentials, authenticate as the channel set
+pub async fn get_feed_content(
+  url: &str,
+  cache: Option<&HttpCache>,
+  auth: Option<&ChannelAuth>,
+) -> Result<FeedContent, FeedError> {
+  request_content(url, cache, auth).await.map_err(|e| {
+    do_log(
+      "Error".to_string(),
+      format!("Err on [get_feed_content: {}]: {}", url, e),
+      format!("{}", Local::now().format("%m/%d/%Y %H:%M:%S")),
+    );
+    e
+  })
+}
+
+// request without logging on error
+async fn request_content(
+  url: &str,
+  cache: Option<&HttpCache>,
+  auth: Option<&ChannelAuth>,
+) -> Result<FeedContent, FeedError> {
+  let mut request = client::client()?.get(url);
+  // not kept on redirects to another host
+  match auth {
+    Some(auth) if auth.kind == "basic" => {
+      request = request.basic_auth(&auth.username, Some(&auth.secret));
+    }
+    Some(auth) if auth.kind == "bearer" => {
+      request = request.bearer_auth(&auth.secret);
+    }
+    _ => {}
+  }
+  if let Some(cache) = cache {
+    if !cache.etag.is_empty() {
+      request = request.header(IF_NONE_MATCH, &cache.etag);
+    }
+    if !cache.last_modified.is_empty() {
+      request = request.header(IF_MODIFIED_SINCE, &cache.last_modified);
+    }
+  }
+  let response = request.send().await?;
+
+  match response.status() {
+    reqwest::StatusCode::OK => {
+      let cache = HttpCache {
+        etag: get_header(response.headers(), ETAG),
+        last_modified: get_header(response.headers(), LAST_MODIFIED),
+      };
+      let content_type = get_header(response.headers(), CONTENT_TYPE);
+      let content = response.bytes().await?;
+
+      Ok(FeedContent::Modified {
+        content,
+        content_type,
+        cache,
+      })
+    }
+    reqwest::StatusCode::NOT_MODIFIED => Ok(FeedContent::NotModified),
+    status => Err(status_error(status)),
+  }
+}
+
+// a response status other than 200 or 304
+fn status_error(status: reqwest::StatusCode) -> FeedError {
+  match status {
+    reqwest::StatusCode::UNAUTHORIZED | reqwest::StatusCode::FORBIDDEN => {
+      FeedError::Unauthorized {
+        code: status.as_u16(),
+      }
+    }
+    _ => FeedError::Status {
+      code: status.as_u16(),
+    },
+  }
+}
+
+// `basic` or `bearer` credentials only, or none
+fn check_auth(auth: Option<&ChannelAuth>) -> Result<(), FeedError> {
+  match auth.map(|a| a.kind.as_str()) {
+    None | Some("basic") | Some("bearer") => Ok(()),
+    Some(kind) => Err(FeedError::UnknownAuth {
+      auth_kind: kind.to_string(),
+    }),
+  }
+}
+
+fn get_header(headers: &HeaderMap, name: HeaderName) -> String {
+  headers
+    .get(name)
+    .and_then(|v| v.to_str().ok())
+    .unwrap_or_default()
+    .to_string()
+}
+
+// 1.1 process rss
+fn process_rss(
+  content: &[u8],
+  url: &str,
+  ty: &str,
+  title: Option<String>,
+) -> Result<(NewChannel, Vec<NewArticle>), FeedError> {
+  match rss::Channel::read_from(content) {
+    Ok(channel) => {
+      let date = channel.pub_date.as_deref().and_then(date::parse_date);
+      let first_seen = date::now();
+      let channel_title = match title {
+        Some(t) if t.trim().len() > 0 => String::from(t.trim()),
+        _ => channel.title.to_string(),
+      };
+      let rss_channel = NewChannel {
+        title: channel_title,
+        link: url.to_string(),
+        description: channel.description.to_string(),
+        published: date,
+        ty: ty.to_string(),
+        etag: String::new(),
+        last_modified: String::new(),
+        image: podcast::read_artwork(&channel),
+        ttl: schedule::read_ttl(&channel),
+      };
+
+      let mut articles: Vec<NewArticle> = Vec::new();
+      for item in channel.items() {
+        let title = item.title.clone().unwrap_or_else(|| String::from(""));
+        let link = item.link.clone().unwrap_or_else(|| String::from(""));
+        let description = item.description.clone().unwrap_or_default();
+        let content = item.content.clone().unwrap_or_else(|| description.clone());
+        // audio, video or image per the mime type
+        let enclosure = item.enclosure.clone().unwrap_or_default();
+        let enclosure_of = |kind: &str| {
+          Some(enclosure.url.clone())
+            .filter(|_| enclosure.mime_type.starts_with(kind))
+        };
+        let media = item.extensions().get("media");
+        let audio_url = enclosure_of("audio/").unwrap_or_default();
+        let video_url = enclosure_of("video/")
+          .or_else(|| media::media_url(media, "video"))
+          .unwrap_or_default();
+        let image = media::media_url(media, "image")
+          .or_else(|| item.itunes_ext().and_then(|i| i.image().map(String::from)))
+          .or_else(|| enclosure_of("image/"))
+          .or_else(|| media::first_image(&content, &link))
+          .unwrap_or_default();
+        let creators = item.dublin_core_ext().map(|dc| dc.creators());
+        let author = media::join_names(
+          item
+            .author()
+            .into_iter()
+            .chain(creators.unwrap_or_default().iter().map(String::as_str))
+            .chain(item.itunes_ext().and_then(|i| i.author())),
+        );
+        let categories =
+          media::join_names(item.categories().iter().map(|c| c.name()));
+        let episode = podcast::read_episode(item);
+        let guid = article_guid(
+          item.guid().map(|g| g.value()),
+          &link,
+          &title,
+          item.pub_date().unwrap_or_default(),
+        );
+
+        let new_article = NewArticle {
+          title,
+          url: link,
+          feed_link: url.to_string(),
+          audio_url,
+          description,
+          published: item
+            .pub_date()
+            .and_then(date::parse_date)
+            .unwrap_or(first_seen),
+          content,
+          author,
+          image,
+          guid,
+          categories,
+          video_url,
+          duration: episode.duration,
+          season: episode.season,
+          episode: episode.episode,
+          explicit: episode.explicit,
+          chapters_url: episode.chapters_url,
+          transcript_url: episode.transcript_url,
+        };
+
+        articles.push(new_article);
+      }
+      Ok((rss_channel, articles))
+    }
+    Err(e) => Err(FeedError::Parse {
+      format: FeedFormat::Rss,
+      message: e.to_string(),
+    }),
+  }
+}
+
+// 1.2- process atom
+fn process_atom(
+  content: &[u8],
+  url: &str,
+  ty: &str,
+  title: Option<String>,
+) -> Result<(NewChannel, Vec<NewArticle>), FeedError> {
+  match atom_syndication::Feed::read_from(content) {
+    Ok(atom) => {
+      let channel_title = match title {
+        Some(t) if t.trim().len() > 0 => String::from(t.trim()),
+        _ => atom.title.to_string(),
+      };
+      let atom_channel = NewChannel {
+        title: channel_title.clone(),
+        link: url.to_string(),
+        description: atom.subtitle.unwrap_or_default().to_string(),
+        published: Some(date::to_utc(atom.updated)),
+        ty: ty.to_string(),
+        etag: String::new(),
+        last_modified: String::new(),
+        image: atom.logo.or(atom.icon).unwrap_or_default(),
+        ttl: None,
+      };
+
+      let mut feeds: Vec<NewArticle> = vec![];
+      for item in atom.entries {
+        let alternate = item
+          .links
+          .iter()
+          .find(|l| l.rel == "alternate")
+          .or(item.links.first());
+        let item_url = alternate.map(|l| l.href.clone()).unwrap_or_default();
+        // audio, video or image per the mime type
+        let enclosure_of = |kind: &str| {
+          item
+            .links
+            .iter()
+            .filter(|l| l.rel == "enclosure")
+            .find(|l| l.mime_type.as_deref().unwrap_or("").starts_with(kind))
+            .map(|l| l.href.clone())
+        };
+        let media = item.extensions.get("media");
+
+        let description = item
+          .summary
+          .as_ref()
+          .map(|s| s.to_string())
+          .or_else(|| media::media_text(media, "description"))
+          .unwrap_or_default();
+        let content = item
+          .content
+          .as_ref()
+          .and_then(|c| c.value.clone())
+          .unwrap_or_else(|| description.clone());
+        let guid = article_guid(
+          Some(&item.id),
+          &item_url,
+          &item.title,
+          &item.updated.to_rfc3339(),
+        );
+
+        let new_article = NewArticle {
+          title: item.title.to_string(),
+          url: item_url.clone(),
+          feed_link: url.to_string(),
+          audio_url: enclosure_of("audio/").unwrap_or_default(),
+          description,
+          published: date::to_utc(item.published.unwrap_or(item.updated)),
+          author: media::join_names(item.authors.iter().map(|a| a.name.as_str())),
+          image: media::media_url(media, "image")
+            .or_else(|| enclosure_of("image/"))
+            .or_else(|| media::first_image(&content, &item_url))
+            .unwrap_or_default(),
+          content,
+          guid,
+          categories: media::join_names(
+            item
+              .categories
+              .iter()
+              .map(|c| c.label.as_deref().unwrap_or(&c.term)),
+          ),
+          video_url: enclosure_of("video/")
+            .or_else(|| media::media_url(media, "video"))
+            .unwrap_or_default(),
+          duration: None,
+          season: None,
+          episode: None,
+          explicit: false,
+          chapters_url: String::new(),
+          transcript_url: String::new(),
+        };
+
+        feeds.push(new_article);
+      }
+      Ok((atom_channel, feeds))
+    }
+    Err(e) => Err(FeedError::Parse {
+      format: FeedFormat::Atom,
+      message: e.to_string(),
+    }),
+  }
+}
+
+// 1.3- process json feed
+fn process_json(
+  content: &[u8],
+  url: &str,
+  ty: &str,
+  title: Option<String>,
+) -> Result<(NewChannel, Vec<NewArticle>), FeedError> {
+  match JsonFeed::read_from(content) {
+    Ok(feed) => {
+      let channel_title = match title {
+        Some(t) if !t.trim().is_empty() => String::from(t.trim()),
+        _ => feed.title,
+      };
+      let json_channel = NewChannel {
+        title: channel_title,
+        link: url.to_string(),
+        description: feed.description.unwrap_or_default(),
+        published: None,
+        ty: ty.to_string(),
+        etag: String::new(),
+        last_modified: String::new(),
+        image: feed.icon.unwrap_or_default(),
+        ttl: None,
+      };
+
+      let first_seen = date::now();
+      let mut articles: Vec<NewArticle> = vec![];
+      for item in feed.items {
+        let author = item.author_names();
+        let description = item
+          .summary
+          .or_else(|| item.content_text.clone())
+          .unwrap_or_default();
+        let content = item
+          .content_html
+          .or(item.content_text)
+          .unwrap_or_else(|| description.clone());
+        // podcast episode as an audio or video attachment
+        let attachment_of = |kind: &str| {
+          item
+            .attachments
+            .iter()
+            .find(|a| a.mime_type.starts_with(kind))
+            .map(|a| a.url.clone())
+            .unwrap_or_default()
+        };
+        let audio_url = attachment_of("audio/");
+        let video_url = attachment_of("video/");
+        let duration = item
+          .attachments
+          .iter()
+          .find(|a| a.url == audio_url || a.url == video_url)
+          .and_then(|a| a.duration_in_seconds)
+          .map(|d| d as i32);
+        let title = item.title.unwrap_or_default();
+        let item_url = item.url.or(item.external_url).unwrap_or_default();
+        let image = item
+          .image
+          .or(item.banner_image)
+          .or_else(|| media::first_image(&content, &item_url))
+          .unwrap_or_default();
+        let date = item.date_published.or(item.date_modified);
+        let guid = article_guid(
+          item.id.as_deref(),
+          &item_url,
+          &title,
+          date.as_deref().unwrap_or_default(),
+        );
+
+        let new_article = NewArticle {
+          title,
+          url: item_url,
+          feed_link: url.to_string(),
+          audio_url,
+          description,
+          published: date
+            .as_deref()
+            .and_then(date::parse_date)
+            .unwrap_or(first_seen),
+          content,
+          author,
+          image,
+          guid,
+          categories: media::join_names(item.tags.iter().map(String::as_str)),
+          video_url,
+          duration,
+          season: None,
+          episode: None,
+          explicit: false,
+          chapters_url: String::new(),
+          transcript_url: String::new(),
+        };
+
+        articles.push(new_article);
+      }
+      Ok((json_channel, articles))
+    }
+    Err(e) => Err(FeedError::Parse {
+      format: FeedFormat::Json,
+      message: e.to_string(),
+    }),
+  }
+}
+
+// identity of an item in its feed: the id if any, or the url,
+// or the title and date if neither
+fn article_guid(id: Option<&str>, url: &str, title: &str, date: &str) -> String {
+  [id.unwrap_or_default(), url]
+    .into_iter()
+    .map(str::trim)
+    .find(|s| !s.is_empty())
+    .map(String::from)
+    .unwrap_or_else(|| format!("{}|{}", title.trim(), date.trim()))
+}
+
+// # end process rss, atom or json feed #
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct FeedResult {
+  pub channel: NewChannel,
+  pub articles: Vec<NewArticle>,
+}
+
+#[command]
+pub async fn fetch_feed(
+  url: String,
+  auth: Option<ChannelAuth>,
+) -> Result<FeedResult, FeedError> {
+  check_auth(auth.as_ref())?;
+  let (channel, articles) =
+    process_feed(&url, "rss", None, auth.as_ref()).await?;
+
+  Ok(FeedResult { channel, articles })
+}
+
+#[command]
+pub async fn add_channel(
+  app: tauri::AppHandle,
+  url: String,
+  ty: String,
+  title: Option<String>,
+  auth: Option<ChannelAuth>,
+) -> Result<usize, FeedError> {
+  check_auth(auth.as_ref())?;
+  let (channel, articles) =
+    process_feed(&url, &ty, title, auth.as_ref()).await?;
+  // the input feed url may not be same as fetched feed link
+  // input feed url as the real rss url
+  // println!("add articles: {:?}", articles.first());
+
+  let link = channel.link.clone();
+  if auth.is_some() {
+    db::set_channel_auth(link.clone(), auth);
+  }
+  let result = db::add_channel(channel, articles);
+  db::record_channel_fetch(link, None);
+  tray::update_unread(&app);
+
+  Ok(result)
+}
+
+#[command]
+pub async fn import_channels(
+  app: tauri::AppHandle,
+  url_list: Vec<String>,
+) -> usize {
+  stream::iter(url_list)
+    .map(|url| add_channel(app.clone(), url, "rss".to_string(), None, None))
+    .buffer_unordered(DEFAULT_CONCURRENCY)
+    .fold(
+      0,
+      |import_num, res| async move { import_num + res.unwrap_or(0) },
+    )
+    .await
+}
+
+// import channels from an OPML file, nested outlines included
+#[command]
+pub async fn import_opml(
+  app: tauri::AppHandle,
+  file_path: String,
+) -> Result<usize, String> {
+  let xml = tokio::fs::read_to_string(&file_path)
+    .await
+    .map_err(|e| e.to_string())?;
+  let feeds = opml::read_opml(&xml).map_err(|e| {
+    do_log(
+      "Error".to_string(),
+      format!("Err on [import_opml: read opml, {}]: {:?}", file_path, e),
+      format!("{}", Local::now().format("%m/%d/%Y %H:%M:%S")),
+    );
+    e.to_string()
+  })?;
+
+  let app = &app;
+  let import_num = stream::iter(feeds)
+    .map(|feed| async move {
+      let (url, title) = (feed.url.clone(), Some(feed.title));
+      let res = add_channel(app.clone(), url, feed.ty, title, None).await;
+      // also for the channel subscribed before
+      let folder = feed.folder.and_then(db::create_folder);
+      let channel = db::get_channel_by_link(feed.url.clone());
+      if let (Some(folder), Some(_)) = (folder, channel) {
+        db::set_channel_folder(feed.url, Some(folder.id));
+      }
+      res.unwrap_or(0)
+    })
+    .buffer_unordered(DEFAULT_CONCURRENCY)
+    .fold(0, |import_num, res| async move { import_num + res })
+    .await;
+
+  Ok(import_num)
+}
+
+// export all channels to an OPML 2.0 file
+#[command]
+pub fn export_opml(file_path: String) -> Result<usize, String> {
+  let channels = db::get_channels();
+  let folders = db::get_folders()
+    .into_iter()
+    .map(|f| (f.id, f.name))
+    .collect::<HashMap<i32, String>>();
+  let folder_of = db::get_channel_folders()
+    .into_iter()
+    .filter_map(|(link, id)| folders.get(&id).map(|name| (link, name.clone())))
+    .collect::<HashMap<String, String>>();
+  let xml = opml::write_opml(&channels, &folder_of).map_err(|e| e.to_string())?;
+  std::fs::write(&file_path, xml).map_err(|e| {
+    do_log(
+      "Error".to_string(),
+      format!("Err on [export_opml: write file, {}]: {:?}", file_path, e),
+      format!("{}", Local::now().format("%m/%d/%Y %H:%M:%S")),
+    );
+    e.to_string()
+  })?;
+
+  Ok(channels.len())
+}
+
+#[command]
+pub async fn get_channels() -> Vec<Channel> {
+  let results = db::get_channels();
+
+  return results;
+}
+
+#[command]
+pub fn delete_channel(link: String) -> usize {
+  db::delete_channel(link)
+}
+
+// refresh a channel, get the number of new articles;
+// the fetch is recorded as the health of the channel
+async fn refresh_channel(
+  app: &tauri::AppHandle,
+  channel: Channel,
+) -> Result<usize, FeedError> {
+  let link = channel.link.clone();
+  let (title, to_notify) = (channel.title.clone(), channel.notify);
+  let full_article = channel.full_article;
+  let result = fetch_new_articles(channel).await;
+  db::record_channel_fetch(
+    link.clone(),
+    result.as_ref().err().map(|e| e.to_string()),
+  );
+
+  let added = result?;
+  let new_num = added.len();
+  let new_titles = added.iter().map(|a| a.title.clone()).collect();
+  if full_article && new_num > 0 {
+    extract_new_articles(added).await;
+  }
+  if to_notify {
+    notify::new_articles(app, title, new_titles);
+  }
+
+  Ok(new_num)
+}
+
+// get the new articles
+async fn fetch_new_articles(
+  channel: Channel,
+) -> Result<Vec<db::AddedArticle>, FeedError> {
+  let cache = HttpCache {
+    etag: channel.etag,
+    last_modified: channel.last_modified,
+  };
+  let auth = db::get_channel_auth(channel.link.clone());
+  let (content, content_type, cache) =
+    match get_feed_content(&channel.link, Some(&cache), auth.as_ref()).await? {
+      FeedContent::Modified {
+        content,
+        content_type,
+        cache,
+      } => (content, content_type, cache),
+      // not modified since last fetch, no new articles
+      FeedContent::NotModified => return Ok(vec![]),
+    };
+  let (fetched, articles) =
+    parse_feed(&content, &content_type, &channel.link, &channel.ty, None)?;
+
+  let result = db::add_articles(String::from(&channel.link), articles)
+    .map_err(|e| FeedError::Db {
+      message: e.to_string(),
+    })?;
+  // only keep the validators of a response we could parse and save
+  db::update_channel_meta(channel.link.clone(), fetched.ttl, fetched.image);
+  db::update_channel_http_cache(channel.link, cache.etag, cache.last_modified);
+
+  Ok(result)
+}
+
+// fail on the channel not fetched, like its credentials rejected
+#[command]
+pub async fn add_articles_with_channel(
+  app: tauri::AppHandle,
+  link: String,
+) -> Result<usize, FeedError> {
+  let channel = db::get_channel_by_link(link);
+  match channel {
+    Some(channel) => {
+      let link = channel.link.clone();
+      let result = refresh_channel(&app, channel).await?;
+      prune_channel(link, &get_global_retention());
+      tray::update_unread(&app);
+      Ok(result)
+    }
+    None => Ok(0),
+  }
+}
+
+// notify the new articles of the channel on refresh
+#[command]
+pub fn set_channel_notify(link: String, notify: bool) -> usize {
+  db::set_channel_notify(link, notify)
+}
+
+// channels failed in a row, at least the min number of times,
+// with the last error
+#[command]
+pub fn get_broken_channels(min_failures: Option<i32>) -> Vec<Channel> {
+  db::get_broken_channels(min_failures.unwrap_or(1))
+}
+
+// # channel auth #
+
+// the kind and username, without the secret
+#[command]
+pub fn get_channel_auth(link: String) -> Option<ChannelAuth> {
+  db::get_channel_auth(link)
+}
+
+// set `basic` or `bearer` credentials, or remove them on None;
+// an empty secret keeps the stored one
+#[command]
+pub fn set_channel_auth(
+  link: String,
+  auth: Option<ChannelAuth>,
+) -> Result<usize, String> {
+  check_auth(auth.as_ref()).map_err(|e| e.to_string())?;
+
+  Ok(db::set_channel_auth(link, auth))
+}
+
+// max number of feeds fetched at the same time
+const DEFAULT_CONCURRENCY: usize = 8;
+const MAX_CONCURRENCY: usize = 32;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct RefreshResult {
+  pub link: String,
+  pub title: String,
+  pub new_num: usize,
+  pub error: Option<String>,
+}
+
+// emitted to window once a channel is refreshed
+#[derive(Debug, Clone, Serialize)]
+pub struct RefreshProgress {
+  pub done: usize,
+  pub total: usize,
+  pub result: RefreshResult,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct RefreshSummary {
+  pub total: usize,
+  pub new_num: usize,
+  pub failed_num: usize,
+  pub pruned_num: usize,
+  pub results: Vec<RefreshResult>,
+}
+
+// refresh all channels, with a bounded concurrency
+// - emit `refresh-progress` event per channel
+// - prune articles per retention at the end
+#[command]
+pub async fn refresh_all_channels(
+  window: tauri::Window,
+  concurrency: Option<usize>,
+) -> RefreshSummary {
+  let channels = db::get_channels();
+  let total = channels.len();
+  let concurrency = concurrency
+    .unwrap_or(DEFAULT_CONCURRENCY)
+    .clamp(1, MAX_CONCURRENCY);
+
+  let app = &window.app_handle();
+  let mut results: Vec<RefreshResult> = Vec::with_capacity(total);
+  let mut refreshing = stream::iter(channels)
+    .map(|channel| async move {
+      let link = channel.link.clone();
+      let title = channel.title.clone();
+      let res = refresh_channel(app, channel).await;
+      RefreshResult {
+        link,
+        title,
+        new_num: *res.as_ref().unwrap_or(&0),
+        error: res.err().map(|e| e.to_string()),
+      }
+    })
+    .buffer_unordered(concurrency);
+
+  while let Some(result) = refreshing.next().await {
+    results.push(result.clone());
+    window
+      .emit(
+        "refresh-progress",
+        RefreshProgress {
+          done: results.len(),
+          total,
+          result,
+        },
+      )
+      .unwrap_or(());
+  }
+
+  let pruned_num = prune(None).removed_num;
+  tray::update_unread(app);
+
+  RefreshSummary {
+    total,
+    new_num: results.iter().map(|r| r.new_num).sum(),
+    failed_num: results.iter().filter(|r| r.error.is_some()).count(),
+    pruned_num,
+    results,
+  }
+}
+
+// # http client #
+
+#[command]
+pub fn get_http_settings() -> client::HttpSettings {
+  client::load_settings()
+}
+
+// used by the next requests, fail on an invalid proxy
+#[command]
+pub fn set_http_settings(settings: client::HttpSettings) -> Result<bool, String> {
+  client::apply_settings(&settings).map_err(|e| {
+    do_log(
+      "Error".to_string(),
+      format!("Err on [set_http_settings]: {}", e),
+      format!("{}", Local::now().format("%m/%d/%Y %H:%M:%S")),
+    );
+    e.to_string()
+  })?;
+
+  Ok(true)
+}
+
+// # schedule #
+
+#[command]
+pub fn get_schedule_settings() -> schedule::ScheduleSettings {
+  schedule::load_settings()
+}
+
+#[command]
+pub fn set_schedule_settings(settings: schedule::ScheduleSettings) -> bool {
+  schedule::save_settings(&settings)
+}
+
+// in minutes, or follow the global interval on None
+#[command]
+pub fn set_channel_refresh_interval(link: String, minutes: Option<i32>) -> usize {
+  db::set_channel_refresh_interval(link, minutes)
+}
+
+// # retention #
+
+// storage key of the global retention
+const RETENTION_KEY: &str = "feed_retention";
+
+// keep all articles by default
+#[command]
+pub fn get_global_retention() -> RetentionPolicy {
+  match storage::get_data(RETENTION_KEY.to_string()) {
+    Ok(data) => serde_json::from_value(data.data).unwrap_or_default(),
+    Err(_) => RetentionPolicy::default(),
+  }
+}
+
+#[command]
+pub fn set_global_retention(policy: RetentionPolicy) -> bool {
+  match serde_json::to_value(policy) {
+    Ok(value) => storage::set_data(RETENTION_KEY.to_string(), value),
+    Err(_) => false,
+  }
+}
+
+// None if the channel follows the global retention
+#[command]
+pub fn get_channel_retention(link: String) -> Option<RetentionPolicy> {
+  db::get_channel_retention(link)
+}
+
+#[command]
+pub fn set_channel_retention(
+  link: String,
+  policy: Option<RetentionPolicy>,
+) -> usize {
+  db::set_channel_retention(link, policy)
+}
+
+fn prune_channel(link: String, global: &RetentionPolicy) -> usize {
+  match db::get_channel_retention(link.clone()) {
+    Some(policy) => db::prune_articles(link, &policy),
+    None => db::prune_articles(link, global),
+  }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct PruneSummary {
+  pub removed_num: usize,
+  pub vacuumed: bool,
+}
+
+// prune articles of a channel or all channels per retention,
+// unread ones too unless kept
+#[command]
+pub fn prune_articles(
+  app: tauri::AppHandle,
+  feed_link: Option<String>,
+) -> PruneSummary {
+  let summary = prune(feed_link);
+  if summary.removed_num > 0 {
+    tray::update_unread(&app);
+  }
+
+  summary
+}
+
+fn prune(feed_link: Option<String>) -> PruneSummary {
+  let links = match feed_link {
+    Some(link) => vec![link],
+    None => db::get_channels().into_iter().map(|c| c.link).collect(),
+  };
+  let global = get_global_retention();
+  let removed_num = links
+    .into_iter()
+    .map(|link| prune_channel(link, &global))
+    .sum();
+
+  PruneSummary {
+    removed_num,
+    vacuumed: removed_num > 0 && db::vacuum_if_needed(),
+  }
+}
+
+// # end retention #
+
+#[command]
+#[allow(clippy::too_many_arguments)]
+pub fn get_articles(
+  feed_link: Option<String>,
+  folder_id: Option<i32>,
+  read_status: Option<i32>,
+  star_status: Option<i32>,
+  since: Option<DateTime<Utc>>,
+  sort: Option<db::SortOrder>,
+  cursor: Option<db::ArticleCursor>,
+  offset: Option<i64>,
+  limit: Option<i64>,
+  summary: Option<bool>,
+  show_hidden: Option<bool>,
+) -> Vec<Article> {
+  db::get_articles(db::ArticleFilter {
+    feed_link,
+    folder_id,
+    read_status,
+    star_status,
+    since,
+    sort,
+    cursor,
+    offset,
+    limit,
+    summary,
+    show_hidden,
+  })
+}
+
+// full-text search, ranked, filtered like get_articles
+#[command]
+#[allow(clippy::too_many_arguments)]
+pub fn search_articles(
+  query: String,
+  feed_link: Option<String>,
+  folder_id: Option<i32>,
+  read_status: Option<i32>,
+  star_status: Option<i32>,
+  since: Option<DateTime<Utc>>,
+  limit: Option<i64>,
+  show_hidden: Option<bool>,
+) -> Vec<db::ArticleSearchResult> {
+  db::search_articles(
+    query,
+    db::ArticleFilter {
+      feed_link,
+      folder_id,
+      read_status,
+      star_status,
+      since,
+      show_hidden,
+      ..Default::default()
+    },
+    limit.unwrap_or(50),
+  )
+}
+
+#[command]
+pub fn get_unread_num() -> HashMap<String, i32> {
+  let record = db::get_unread_num();
+  let result = record
+    .into_iter()
+    .map(|r| (r.feed_link.clone(), r.unread_count.clone()))
+    .collect::<HashMap<String, i32>>();
+
+  result
+}
+
+#[command]
+pub fn get_folder_unread_num() -> HashMap<i32, i32> {
+  db::get_folder_unread_num()
+    .into_iter()
+    .map(|r| (r.folder_id, r.unread_count))
+    .collect::<HashMap<i32, i32>>()
+}
+
+#[command]
+pub fn get_folders() -> Vec<Folder> {
+  db::get_folders()
+}
+
+#[command]
+pub fn create_folder(name: String) -> Option<Folder> {
+  db::create_folder(name)
+}
+
+#[command]
+pub fn rename_folder(id: i32, name: String) -> usize {
+  db::rename_folder(id, name)
+}
+
+#[command]
+pub fn delete_folder(id: i32) -> usize {
+  db::delete_folder(id)
+}
+
+// folder id per channel link
+#[command]
+pub fn get_channel_folders() -> HashMap<String, i32> {
+  db::get_channel_folders().into_iter().collect()
+}
+
+// move channel to the folder, or out of any folder if no folder_id;
+// 0 on an unknown folder
+#[command]
+pub fn set_channel_folder(link: String, folder_id: Option<i32>) -> usize {
+  db::set_channel_folder(link, folder_id)
+}
+
+#[command]
+pub fn get_article_by_url(url: String) -> Option<Article> {
+  db::get_article_by_url(url)
+}
+
+#[command]
+pub fn update_article_read_status(
+  app: tauri::AppHandle,
+  url: String,
+  status: i32,
+) -> usize {
+  let result = db::update_article_read_status(url, status);
+  tray::update_unread(&app);
+
+  result
+}
+
+#[command]
+pub fn update_article_star_status(url: String, status: i32) -> usize {
+  db::update_article_star_status(url, status)
+}
+
+// per id, as an url can be shared by articles
+
+#[command]
+pub fn get_article_by_id(id: i32) -> Option<Article> {
+  db::get_article_by_id(id)
+}
+
+#[command]
+pub fn update_article_read_status_by_id(
+  app: tauri::AppHandle,
+  id: i32,
+  status: i32,
+) -> usize {
+  let result = db::update_article_read_status_by_id(id, status);
+  tray::update_unread(&app);
+
+  result
+}
+
+#[command]
+pub fn update_article_star_status_by_id(id: i32, status: i32) -> usize {
+  db::update_article_star_status_by_id(id, status)
+}
+
+#[command]
+pub fn update_all_read_status(
+  app: tauri::AppHandle,
+  feed_link: String,
+  read_status: i32,
+) -> usize {
+  let result = db::update_articles_read_status(feed_link, read_status);
+  tray::update_unread(&app);
+
+  result
+}
+
+// # podcast playback #
+
+#[command]
+pub fn save_playback(
+  audio_url: String,
+  position: f64,
+  duration: Option<f64>,
+) -> usize {
+  db::save_playback(audio_url, position, duration)
+}
+
+#[command]
+pub fn get_playback(audio_url: String) -> Option<Playback> {
+  db::get_playback(audio_url)
+}
+
+#[command]
+pub fn get_playbacks() -> Vec<Playback> {
+  db::get_playbacks()
+}
+
+// the played episode might be deleted, per the cache settings
+#[command]
+pub async fn set_playback_completed(
+  audio_url: String,
+  completed: bool,
+) -> usize {
+  let result = db::set_playback_completed(audio_url, completed);
+  if completed {
+    clean_episode_cache().await;
+  }
+  result
+}
+
+#[command]
+pub fn get_play_queue() -> Vec<QueueItem> {
+  db::get_play_queue()
+}
+
+#[command]
+pub fn add_to_queue(item: QueueItem, index: Option<i32>) -> usize {
+  db::add_to_queue(item, index)
+}
+
+#[command]
+pub fn remove_from_queue(audio_url: String) -> usize {
+  db::remove_from_queue(audio_url)
+}
+
+#[command]
+pub fn reorder_queue(audio_urls: Vec<String>) -> usize {
+  db::reorder_queue(audio_urls)
+}
+
+#[command]
+pub fn clear_queue() -> usize {
+  db::clear_queue()
+}
+
+// storage key of the episode cache settings
+const EPISODE_CACHE_KEY: &str = "episode_cache";
+
+#[command]
+pub fn get_episode_cache_settings() -> download::EpisodeCacheSettings {
+  match storage::get_data(EPISODE_CACHE_KEY.to_string()) {
+    Ok(data) => serde_json::from_value(data.data).unwrap_or_default(),
+    Err(_) => download::EpisodeCacheSettings::default(),
+  }
+}
+
+#[command]
+pub fn set_episode_cache_settings(
+  settings: download::EpisodeCacheSettings,
+) -> bool {
+  match serde_json::to_value(settings) {
+    Ok(value) => storage::set_data(EPISODE_CACHE_KEY.to_string(), value),
+    Err(_) => false,
+  }
+}
+
+// emitted to window while downloading an episode
+#[derive(Debug, Clone, Serialize)]
+pub struct DownloadProgress {
+  pub article_id: i32,
+  pub downloaded: u64,
+  pub total: Option<u64>,
+}
+
+// download the audio of an article, get the local path
+// - emit `episode-download-progress` event
+#[command]
+pub async fn download_episode(
+  window: tauri::Window,
+  article_id: i32,
+) -> Result<String, String> {
+  // emit at most per 256 KB
+  const PROGRESS_STEP: u64 = 256 * 1024;
+
+  let article = db::get_article_by_id(article_id)
+    .ok_or_else(|| format!("no article of id: {}", article_id))?;
+  if article.audio_url.is_empty() {
+    return Err("no audio to download".to_string());
+  }
+  if !article.local_audio_path.is_empty()
+    && std::path::Path::new(&article.local_audio_path).is_file()
+  {
+    return Ok(article.local_audio_path);
+  }
+
+  // till the path is saved, so the cache cleaning keeps the file
+  let _downloading = download::Downloading::start(article_id)
+    .ok_or_else(|| "the episode is downloading already".to_string())?;
+  let dir = download::episodes_dir().await.map_err(|e| e.to_string())?;
+  let path = download::episode_path(&dir, article_id, &article.audio_url);
+  let mut emitted: u64 = 0;
+  download::download(&article.audio_url, &path, |downloaded, total| {
+    if downloaded - emitted < PROGRESS_STEP && Some(downloaded) != total {
+      return;
+    }
+    emitted = downloaded;
+    window
+      .emit(
+        "episode-download-progress",
+        DownloadProgress {
+          article_id,
+          downloaded,
+          total,
+        },
+      )
+      .unwrap_or(());
+  })
+  .await
+  .map_err(|e| {
+    do_log(
+      "Error".to_string(),
+      format!("Err on [download_episode: {}]: {}", article.audio_url, e),
+      format!("{}", Local::now().format("%m/%d/%Y %H:%M:%S")),
+    );
+    e.to_string()
+  })?;
+
+  let local_path = path.to_string_lossy().to_string();
+  db::set_local_audio_path(article_id, local_path.clone());
+  clean_episode_cache().await;
+
+  Ok(local_path)
+}
+
+#[command]
+pub async fn delete_episode(article_id: i32) -> usize {
+  match db::get_article_by_id(article_id) {
+    Some(article) if !article.local_audio_path.is_empty() => {
+      tokio::fs::remove_file(&article.local_audio_path)
+        .await
+        .unwrap_or(());
+      db::set_local_audio_path(article_id, String::new())
+    }
+    _ => 0,
+  }
+}
+
+// size of the downloaded episodes, in bytes
+#[command]
+pub async fn get_episode_cache_size() -> u64 {
+  match download::episodes_dir().await {
+    Ok(dir) => download::cache_size(&dir).await,
+    Err(_) => 0,
+  }
+}
+
+// delete the played episodes per the cache settings and the files
+// of the gone articles, get the number of deleted episodes
+#[command]
+pub async fn clean_episode_cache() -> usize {
+  let dir = match download::episodes_dir().await {
+    Ok(dir) => dir,
+    Err(_) => return 0,
+  };
+  download::remove_unsaved(&dir, || {
+    db::get_downloaded_episodes()
+      .into_iter()
+      .map(|e| e.local_audio_path)
+      .collect()
+  })
+  .await;
+
+  let settings = get_episode_cache_settings();
+  let episodes = db::get_downloaded_episodes();
+  let mut size = download::cache_size(&dir).await;
+  let mut deleted_num = 0;
+  for episode in episodes.iter().filter(|e| e.completed) {
+    let over_limit = settings.max_bytes().map(|max| size > max).unwrap_or(false);
+    if !settings.delete_played && !over_limit {
+      break;
+    }
+    let path = std::path::Path::new(&episode.local_audio_path);
+    size -= download::file_size(path).await.min(size);
+    tokio::fs::remove_file(path).await.unwrap_or(());
+    db::set_local_audio_path(episode.id, String::new());
+    deleted_num += 1;
+  }
+
+  deleted_num
+}
+
+// # end podcast playback #
+
+// # filter rules #
+
+#[command]
+pub fn get_filter_rules() -> Vec<FilterRule> {
+  db::get_filter_rules()
+}
+
+// applied to the articles fetched since
+#[command]
+pub fn create_filter_rule(rule: NewFilterRule) -> Result<FilterRule, String> {
+  filter::Rule::new(&rule)?;
+  db::create_filter_rule(rule)
+    .ok_or_else(|| "failed to save the rule".to_string())
+}
+
+#[command]
+pub fn delete_filter_rule(id: i32) -> usize {
+  db::delete_filter_rule(id)
+}
+
+// the existing articles a rule would match, the newest first
+#[command]
+pub fn test_filter_rule(
+  rule: NewFilterRule,
+  limit: Option<usize>,
+) -> Result<Vec<Article>, String> {
+  let rule = filter::Rule::new(&rule)?;
+  Ok(db::match_filter_rule(&rule, limit.unwrap_or(50)))
+}
+
+// re-apply a rule, or all rules on None, to the existing articles
+#[command]
+pub fn apply_filter_rules(
+  app: tauri::AppHandle,
+  rule_id: Option<i32>,
+) -> db::FilterSummary {
+  let rules = db::get_filter_rules()
+    .into_iter()
+    .filter(|rule| rule_id.map(|id| id == rule.id).unwrap_or(true))
+    .collect();
+  let summary = db::apply_filter_rules(&filter::RuleSet::new(rules));
+  tray::update_unread(&app);
+
+  summary
+}
+
+// # end filter rules #
+
+// # full article #
+
+// max new articles of a channel extracted per refresh
+const MAX_EXTRACT_NUM: usize = 10;
+const EXTRACT_CONCURRENCY: usize = 4;
+
+// download the article page, keep its main content as the content
+#[command]
+pub async fn fetch_full_article(article_id: i32) -> Result<Article, String> {
+  let article = db::get_article_by_id(article_id)
+    .ok_or_else(|| format!("article not found: {}", article_id))?;
+  if article.url.is_empty() {
+    return Err("article without url".to_string());
+  }
+  extract_full_article(article_id, &article.url)
+    .await
+    .map_err(|e| e.to_string())?;
+
+  db::get_article_by_id(article_id)
+    .ok_or_else(|| format!("article not found: {}", article_id))
+}
+
+// extract the full content of the new articles of the channel on refresh
+#[command]
+pub fn set_channel_full_article(link: String, full_article: bool) -> usize {
+  db::set_channel_full_article(link, full_article)
+}
+
+async fn extract_full_article(id: i32, url: &str) -> Result<usize, FeedError> {
+  let content = readability::fetch(url).await.map_err(|e| {
+    do_log(
+      "Error".to_string(),
+      format!("Err on [extract_full_article: {}]: {}", url, e),
+      format!("{}", Local::now().format("%m/%d/%Y %H:%M:%S")),
+    );
+    e
+  })?;
+
+  Ok(db::set_article_full_content(id, content))
+}
+
+// extract the articles just added, in the feed order;
+// the failed ones keep the content of the feed
+async fn extract_new_articles(added: Vec<db::AddedArticle>) {
+  let to_extract = added
+    .into_iter()
+    .filter(|a| !a.url.is_empty())
+    .take(MAX_EXTRACT_NUM);
+  stream::iter(to_extract)
+    .for_each_concurrent(EXTRACT_CONCURRENCY, |a| async move {
+      extract_full_article(a.id, &a.url).await.unwrap_or(0);
+    })
+    .await;
+}
+
+// # end full article #
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_delete_channel() {
+    let url = "https://mdsilo.com";
+    delete_channel(String::from(url));
+  }
+
+  #[test]
+  fn test_get_unread_num() {
+    get_unread_num();
+  }
+
+  #[test]
+  fn test_parse_feed() {
+    let rss = r#"<rss version="2.0"><channel>
+      <title>mdSilo</title><link>https://mdsilo.com</link><description>rss</description>
+      <item><title>Hello</title><link>https://mdsilo.com/hello</link></item>
+      <item><title>Note 1</title><guid isPermaLink="false">note-1</guid></item>
+      <item><title>Note 2</title></item>
+    </channel></rss>"#;
+    let (channel, articles) = parse_feed(
+      rss.as_bytes(),
+      "text/xml",
+      "https://mdsilo.com/rss.xml",
+      "rss",
+      None,
+    )
+    .unwrap();
+    assert_eq!(channel.title, "mdSilo");
+    assert_eq!(channel.link, "https://mdsilo.com/rss.xml");
+    assert_eq!(articles[0].url, "https://mdsilo.com/hello");
+    // identity without link
+    assert_eq!(articles[0].guid, "https://mdsilo.com/hello");
+    assert_eq!(articles[1].guid, "note-1");
+    assert_eq!(articles[2].guid, "Note 2|");
+
+    let atom = r#"<feed xmlns="http://www.w3.org/2005/Atom">
+      <title>mdSilo</title><id>urn:mdsilo</id><updated>2024-03-06T00:00:00Z</updated>
+      <entry><title>Hello</title><id>urn:hello</id><updated>2024-03-06T00:00:00Z</updated>
+      <link href="https://mdsilo.com/hello"/></entry>
+    </feed>"#;
+    let (channel, articles) = parse_feed(
+      atom.as_bytes(),
+      "application/xml",
+      "https://mdsilo.com/atom.xml",
+      "rss",
+      None,
+    )
+    .unwrap();
+    assert_eq!(channel.title, "mdSilo");
+    assert_eq!(articles[0].title, "Hello");
+    assert_eq!(articles[0].guid, "urn:hello");
+
+    let json = r#"{
+      "version": "https://jsonfeed.org/version/1.1",
+      "title": "mdSilo Podcast",
+      "items": [{
+        "id": "1",
+        "url": "https://mdsilo.com/ep1",
+        "title": "Episode 1",
+        "content_text": "Hello",
+        "image": "https://mdsilo.com/ep1.png",
+        "date_published": "2024-03-06T00:00:00Z",
+        "authors": [{ "name": "dloh" }],
+        "attachments": [{ "url": "https://mdsilo.com/ep1.mp3", "mime_type": "audio/mpeg" }]
+      }]
+    }"#;
+    let (channel, articles) = parse_feed(
+      json.as_bytes(),
+      "application/feed+json",
+      "https://mdsilo.com/feed.json",
+      "podcast",
+      None,
+    )
+    .unwrap();
+    assert_eq!(channel.title, "mdSilo Podcast");
+    assert_eq!(articles[0].content, "Hello");
+    assert_eq!(articles[0].author, "dloh");
+    assert_eq!(articles[0].image, "https://mdsilo.com/ep1.png");
+    assert_eq!(articles[0].audio_url, "https://mdsilo.com/ep1.mp3");
+  }
+
+  #[test]
+  fn test_parse_media() {
+    let rss = r#"<rss version="2.0" xmlns:media="http://search.yahoo.com/mrss/"
+      xmlns:dc="http://purl.org/dc/elements/1.1/"><channel>
+      <title>mdSilo</title><link>https://mdsilo.com</link><description>rss</description>
+      <item><title>Hello</title><link>https://mdsilo.com/hello</link>
+        <category>Rust</category><category>Tauri</category>
+        <dc:creator>dloh</dc:creator>
+        <media:group><media:thumbnail url="https://mdsilo.com/hello.png"/></media:group>
+        <enclosure url="https://mdsilo.com/hello.mp4" length="1" type="video/mp4"/>
+      </item>
+      <item><title>Image</title><link>https://mdsilo.com/blog/image</link>
+        <description><![CDATA[<p><img src="/image.png"></p>]]></description>
+      </item>
+    </channel></rss>"#;
+    let (_, articles) = parse_feed(
+      rss.as_bytes(),
+      "",
+      "https://mdsilo.com/rss.xml",
+      "rss",
+      None,
+    )
+    .unwrap();
+    assert_eq!(articles[0].categories, "Rust, Tauri");
+    assert_eq!(articles[0].author, "dloh");
+    assert_eq!(articles[0].image, "https://mdsilo.com/hello.png");
+    assert_eq!(articles[0].video_url, "https://mdsilo.com/hello.mp4");
+    assert_eq!(articles[0].audio_url, "");
+    assert_eq!(articles[1].image, "https://mdsilo.com/image.png");
+
+    let atom = r#"<feed xmlns="http://www.w3.org/2005/Atom">
+      <title>mdSilo</title><id>urn:mdsilo</id><updated>2024-03-06T00:00:00Z</updated>
+      <entry><title>Hello</title><id>urn:hello</id><updated>2024-03-06T00:00:00Z</updated>
+        <link rel="enclosure" type="audio/mpeg" href="https://mdsilo.com/hello.mp3"/>
+        <link rel="alternate" href="https://mdsilo.com/hello"/>
+        <author><name>dloh</name></author><author><name>mdSilo</name></author>
+        <category term="rust" label="Rust"/>
+      </entry>
+    </feed>"#;
+    let (_, articles) = parse_feed(
+      atom.as_bytes(),
+      "",
+      "https://mdsilo.com/atom.xml",
+      "rss",
+      None,
+    )
+    .unwrap();
+    assert_eq!(articles[0].url, "https://mdsilo.com/hello");
+    assert_eq!(articles[0].audio_url, "https://mdsilo.com/hello.mp3");
+    assert_eq!(articles[0].author, "dloh, mdSilo");
+    assert_eq!(articles[0].categories, "Rust");
+  }
+
+  #[test]
+  fn test_parse_podcast() {
+    let rss = r#"<rss version="2.0"
+      xmlns:itunes="http://www.itunes.com/dtds/podcast-1.0.dtd"
+      xmlns:podcast="https://podcastindex.org/namespace/1.0"><channel>
+      <title>mdSilo Podcast</title><link>https://mdsilo.com</link><description>rss</description>
+      <itunes:image href="https://mdsilo.com/podcast.png"/>
+      <item><title>Episode 2</title><guid>ep2</guid>
+        <itunes:duration>1:02:03</itunes:duration>
+        <itunes:episode>2</itunes:episode>
+        <itunes:explicit>yes</itunes:explicit>
+        <podcast:season>1</podcast:season>
+        <podcast:chapters url="https://mdsilo.com/ep2.json" type="application/json+chapters"/>
+        <podcast:transcript url="https://mdsilo.com/ep2.html" type="text/html"/>
+        <podcast:transcript url="https://mdsilo.com/ep2.vtt" type="text/vtt"/>
+      </item>
+    </channel></rss>"#;
+    let (channel, articles) = parse_feed(
+      rss.as_bytes(),
+      "",
+      "https://mdsilo.com/podcast.xml",
+      "podcast",
+      None,
+    )
+    .unwrap();
+    assert_eq!(channel.image, "https://mdsilo.com/podcast.png");
+    assert_eq!(articles[0].duration, Some(3723));
+    assert_eq!(articles[0].season, Some(1));
+    assert_eq!(articles[0].episode, Some(2));
+    assert!(articles[0].explicit);
+    assert_eq!(articles[0].chapters_url, "https://mdsilo.com/ep2.json");
+    assert_eq!(articles[0].transcript_url, "https://mdsilo.com/ep2.vtt");
+  }
+
+  #[test]
+  fn test_detect_format() {
+    let rss = b"<?xml version=\"1.0\"?>\n<!-- <feed> --><rss version=\"2.0\">";
+    assert_eq!(detect_format("text/html", rss), Some(FeedFormat::Rss));
+    let atom = b"\xEF\xBB\xBF<feed xmlns=\"http://www.w3.org/2005/Atom\">";
+    assert_eq!(
+      detect_format("application/rss+xml", atom),
+      Some(FeedFormat::Atom)
+    );
+    assert_eq!(
+      detect_format("application/json", b" {}"),
+      Some(FeedFormat::Json)
+    );
+    assert_eq!(
+      detect_format("application/atom+xml; charset=utf-8", b"<x/>"),
+      Some(FeedFormat::Atom)
+    );
+    assert_eq!(detect_format("text/html", b"<html></html>"), None);
+  }
+
+  #[test]
+  fn test_channel_auth() {
+    let auth = |kind: &str| ChannelAuth {
+      kind: kind.to_string(),
+      ..ChannelAuth::default()
+    };
+    assert!(check_auth(None).is_ok());
+    assert!(check_auth(Some(&auth("basic"))).is_ok());
+    assert!(check_auth(Some(&auth("bearer"))).is_ok());
+    assert_eq!(
+      check_auth(Some(&auth("digest"))).unwrap_err().to_string(),
+      "unknown auth kind: digest"
+    );
+
+    let status = |code: u16| reqwest::StatusCode::from_u16(code).unwrap();
+    assert!(matches!(
+      status_error(status(401)),
+      FeedError::Unauthorized { code: 401 }
+    ));
+    assert!(matches!(
+      status_error(status(403)),
+      FeedError::Unauthorized { code: 403 }
+    ));
+    assert!(matches!(
+      status_error(status(404)),
+      FeedError::Status { code: 404 }
+    ));
+    assert_eq!(
+      status_error(status(401)).to_string(),
+      "authentication required (401), check the credentials"
+    );
+  }
+}
@@ -0,0 +1,130 @@
+// native notifications of the new articles, for channels to notify
+//
+// at most one per minute: the new articles in between are summed up
+// into the next one, so refreshing lots of channels does not spam
+
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use tauri::api::notification::Notification;
+use tauri::AppHandle;
+
+const MIN_GAP: Duration = Duration::from_secs(60);
+// wait a bit for the channels refreshed at the same time
+const BATCH_DELAY: Duration = Duration::from_secs(3);
+// titles listed in a notification of one channel
+const MAX_TITLES: usize = 3;
+
+struct Pending {
+  // channel title, and the titles of its new articles
+  channels: Vec<(String, Vec<String>)>,
+  last_shown: Option<Instant>,
+  // a notification is on the way
+  scheduled: bool,
+}
+
+static PENDING: Mutex<Pending> = Mutex::new(Pending {
+  channels: Vec::new(),
+  last_shown: None,
+  scheduled: false,
+});
+
+pub fn new_articles(app: &AppHandle, channel: String, titles: Vec<String>) {
+  if titles.is_empty() {
+    return;
+  }
+
+  let wait = match PENDING.lock() {
+    Ok(mut pending) => {
+      match pending.channels.iter_mut().find(|(c, _)| *c == channel) {
+        Some((_, pending_titles)) => pending_titles.extend(titles),
+        None => pending.channels.push((channel, titles)),
+      }
+      if pending.scheduled {
+        return;
+      }
+      pending.scheduled = true;
+      pending
+        .last_shown
+        .map(|shown| MIN_GAP.saturating_sub(shown.elapsed()))
+        .unwrap_or_default()
+        .max(BATCH_DELAY)
+    }
+    Err(_) => return,
+  };
+
+  let app = app.clone();
+  tauri::async_runtime::spawn(async move {
+    tokio::time::sleep(wait).await;
+    show(&app);
+  });
+}
+
+fn show(app: &AppHandle) {
+  let channels = match PENDING.lock() {
+    Ok(mut pending) => {
+      pending.scheduled = false;
+      pending.last_shown = Some(Instant::now());
+      std::mem::take(&mut pending.channels)
+    }
+    Err(_) => return,
+  };
+  if let Some((title, body)) = summarize(&channels) {
+    Notification::new(&app.config().tauri.bundle.identifier)
+      .title(title)
+      .body(body)
+      .show()
+      .unwrap_or(());
+  }
+}
+
+// title and body of the notification:
+// the new titles of one channel, or the new numbers per channel
+pub fn summarize(channels: &[(String, Vec<String>)]) -> Option<(String, String)> {
+  match channels {
+    [] => None,
+    [(channel, titles)] => {
+      let mut lines: Vec<String> = titles.iter().take(MAX_TITLES).cloned().collect();
+      if titles.len() > MAX_TITLES {
+        lines.push(format!("and {} more", titles.len() - MAX_TITLES));
+      }
+      Some((channel.clone(), lines.join("\n")))
+    }
+    _ => {
+      let total: usize = channels.iter().map(|(_, titles)| titles.len()).sum();
+      let body = channels
+        .iter()
+        .map(|(channel, titles)| format!("{} ({})", channel, titles.len()))
+        .collect::<Vec<String>>()
+        .join(", ");
+      Some((format!("{} new articles", total), body))
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_notify_summary() {
+    let titles = |n: usize| (1..=n).map(|i| format!("Post {}", i)).collect();
+    assert_eq!(summarize(&[]), None);
+    assert_eq!(
+      summarize(&[("Blog".to_string(), titles(5))]),
+      Some((
+        "Blog".to_string(),
+        "Post 1\nPost 2\nPost 3\nand 2 more".to_string()
+      ))
+    );
+    assert_eq!(
+      summarize(&[
+        ("Blog".to_string(), titles(2)),
+        ("News".to_string(), titles(1)),
+      ]),
+      Some((
+        "3 new articles".to_string(),
+        "Blog (2), News (1)".to_string()
+      ))
+    );
+  }
+}
@@ -0,0 +1,197 @@
+// OPML import and export of channels
+// spec: http://opml.org/spec2.opml
+
+use chrono::Utc;
+use opml::{Head, Outline, OPML};
+use std::collections::{BTreeMap, HashMap};
+
+use crate::models::Channel;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct OpmlFeed {
+  pub url: String,
+  pub title: String,
+  pub ty: String, // podcast || rss
+  // nested outlines as folder, names joined by `/`
+  pub folder: Option<String>,
+}
+
+// read all feeds, walking into nested outlines
+pub fn read_opml(xml: &str) -> Result<Vec<OpmlFeed>, opml::Error> {
+  let document = OPML::from_str(xml)?;
+  let mut feeds: Vec<OpmlFeed> = vec![];
+  walk_outlines(&document.body.outlines, &mut vec![], &mut feeds);
+
+  Ok(feeds)
+}
+
+fn walk_outlines(
+  outlines: &[Outline],
+  folders: &mut Vec<String>,
+  feeds: &mut Vec<OpmlFeed>,
+) {
+  for outline in outlines {
+    match outline.xml_url.as_deref().map(str::trim) {
+      Some(url) if !url.is_empty() => {
+        let title = outline
+          .title
+          .clone()
+          .filter(|t| !t.trim().is_empty())
+          .unwrap_or_else(|| outline.text.clone());
+        feeds.push(OpmlFeed {
+          url: url.to_string(),
+          title: title.trim().to_string(),
+          ty: outline_ty(outline).to_string(),
+          folder: Some(folders.join("/")).filter(|f| !f.is_empty()),
+        });
+      }
+      // an outline without xmlUrl is a folder
+      _ => {
+        folders.push(outline.text.trim().to_string());
+        walk_outlines(&outline.outlines, folders, feeds);
+        folders.pop();
+      }
+    }
+  }
+}
+
+// type is `rss` for any feed, podcast marked by type or category
+fn outline_ty(outline: &Outline) -> &'static str {
+  let is_podcast = |s: &Option<String>| {
+    s.as_deref()
+      .map(|s| s.to_lowercase().contains("podcast"))
+      .unwrap_or(false)
+  };
+  if is_podcast(&outline.r#type) || is_podcast(&outline.category) {
+    "podcast"
+  } else {
+    "rss"
+  }
+}
+
+// channels in folder are nested in an outline of the folder name,
+// the nested folders like `Tech/Rust` rebuilt as nested outlines
+pub fn write_opml(
+  channels: &[Channel],
+  folder_of: &HashMap<String, String>,
+) -> Result<String, opml::Error> {
+  let mut document = OPML {
+    head: Some(Head {
+      title: Some("mdSilo Subscriptions".to_string()),
+      date_created: Some(Utc::now().to_rfc2822()),
+      ..Head::default()
+    }),
+    ..OPML::default()
+  };
+
+  let mut root = FolderOutline::default();
+  for channel in channels {
+    let mut node = &mut root;
+    if let Some(folder) = folder_of.get(&channel.link) {
+      for name in folder.split('/').map(str::trim).filter(|n| !n.is_empty()) {
+        node = node.folders.entry(name).or_default();
+      }
+    }
+    node.outlines.push(channel_outline(channel));
+  }
+  document.body.outlines = root.into_outlines();
+
+  document.to_string()
+}
+
+// the channels of a folder, then its subfolders
+#[derive(Default)]
+struct FolderOutline<'a> {
+  outlines: Vec<Outline>,
+  folders: BTreeMap<&'a str, FolderOutline<'a>>,
+}
+
+impl FolderOutline<'_> {
+  fn into_outlines(self) -> Vec<Outline> {
+    let mut outlines = self.outlines;
+    for (name, folder) in self.folders {
+      outlines.push(Outline {
+        text: name.to_string(),
+        title: Some(name.to_string()),
+        outlines: folder.into_outlines(),
+        ..Outline::default()
+      });
+    }
+
+    outlines
+  }
+}
+
+fn channel_outline(channel: &Channel) -> Outline {
+  Outline {
+    text: channel.title.clone(),
+    title: Some(channel.title.clone()),
+    r#type: Some("rss".to_string()),
+    xml_url: Some(channel.link.clone()),
+    description: Some(channel.description.clone()).filter(|d| !d.is_empty()),
+    category: Some("podcast".to_string()).filter(|_| channel.ty == "podcast"),
+    ..Outline::default()
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_opml() {
+    let xml = r#"<?xml version="1.0" encoding="UTF-8"?>
+    <opml version="2.0"><head><title>Subscriptions</title></head><body>
+      <outline text="mdSilo" type="rss" xmlUrl="https://mdsilo.com/rss.xml"/>
+      <outline text="Tech">
+        <outline text="Rust">
+          <outline text="This Week in Rust" title="TWiR" type="rss"
+            xmlUrl="https://this-week-in-rust.org/rss.xml"/>
+        </outline>
+        <outline text="Podcast" type="rss" category="podcast"
+          xmlUrl="https://mdsilo.com/podcast.xml"/>
+      </outline>
+    </body></opml>"#;
+    let feeds = read_opml(xml).unwrap();
+    assert_eq!(feeds.len(), 3);
+    assert_eq!(feeds[1].title, "TWiR");
+    assert_eq!(feeds[1].url, "https://this-week-in-rust.org/rss.xml");
+    assert_eq!(feeds[0].ty, "rss");
+    assert_eq!(feeds[2].ty, "podcast");
+
+    let channel = Channel {
+      id: 1,
+      title: "Podcast".to_string(),
+      link: "https://mdsilo.com/podcast.xml".to_string(),
+      description: String::new(),
+      published: None,
+      ty: "podcast".to_string(),
+      etag: String::new(),
+      last_modified: String::new(),
+      image: String::new(),
+      last_fetched: None,
+      last_success: None,
+      failure_count: 0,
+      last_error: String::new(),
+      ttl: None,
+      refresh_interval: None,
+      notify: false,
+      full_article: false,
+    };
+    assert_eq!(feeds[1].folder.as_deref(), Some("Tech/Rust"));
+    assert_eq!(feeds[2].folder.as_deref(), Some("Tech"));
+
+    let folder_of = HashMap::from([(channel.link.clone(), "Tech".to_string())]);
+    let exported = write_opml(std::slice::from_ref(&channel), &folder_of).unwrap();
+    assert_eq!(read_opml(&exported).unwrap(), vec![feeds[2].clone()]);
+
+    // nested folders are exported as nested outlines
+    let folder_of = HashMap::from([(channel.link.clone(), "Tech/Rust".to_string())]);
+    let exported = write_opml(&[channel], &folder_of).unwrap();
+    assert!(!exported.contains("Tech/Rust"));
+    assert_eq!(
+      read_opml(&exported).unwrap()[0].folder.as_deref(),
+      Some("Tech/Rust")
+    );
+  }
+}
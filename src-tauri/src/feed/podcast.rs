@@ -0,0 +1,118 @@
+// podcast metadata, per the iTunes and Podcasting 2.0 namespaces
+// spec: https://help.apple.com/itc/podcasts_connect/#/itcb54353390
+//       https://podcastindex.org/namespace/1.0
+
+use rss::extension::Extension;
+
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct Episode {
+  pub duration: Option<i32>, // seconds
+  pub season: Option<i32>,
+  pub episode: Option<i32>,
+  pub explicit: bool,
+  pub chapters_url: String,
+  pub transcript_url: String,
+}
+
+// transcript formats, the preferred first
+const TRANSCRIPT_TYPES: [&str; 4] = [
+  "text/vtt",
+  "application/x-subrip",
+  "application/srt",
+  "text/html",
+];
+
+pub fn read_episode(item: &rss::Item) -> Episode {
+  let itunes = item.itunes_ext();
+  let podcast = item.extensions().get("podcast");
+  let elements = |name: &str| -> Vec<&Extension> {
+    podcast
+      .and_then(|p| p.get(name))
+      .map(|els| els.iter().collect())
+      .unwrap_or_default()
+  };
+  let number = |itunes_value: Option<&str>, name: &str| {
+    itunes_value.and_then(parse_number).or_else(|| {
+      elements(name)
+        .iter()
+        .find_map(|e| e.value().and_then(parse_number))
+    })
+  };
+
+  let mut transcripts = elements("transcript");
+  transcripts.sort_by_key(|e| {
+    let ty = e.attrs.get("type").map(String::as_str).unwrap_or_default();
+    TRANSCRIPT_TYPES
+      .iter()
+      .position(|t| *t == ty)
+      .unwrap_or(TRANSCRIPT_TYPES.len())
+  });
+  let url_of = |els: &[&Extension]| {
+    els
+      .iter()
+      .find_map(|e| e.attrs.get("url"))
+      .cloned()
+      .unwrap_or_default()
+  };
+
+  Episode {
+    duration: itunes.and_then(|i| i.duration()).and_then(parse_duration),
+    season: number(itunes.and_then(|i| i.season()), "season"),
+    episode: number(itunes.and_then(|i| i.episode()), "episode"),
+    explicit: itunes
+      .and_then(|i| i.explicit())
+      .map(is_explicit)
+      .unwrap_or(false),
+    chapters_url: url_of(&elements("chapters")),
+    transcript_url: url_of(&transcripts),
+  }
+}
+
+// artwork of the podcast, or the image of the channel
+pub fn read_artwork(channel: &rss::Channel) -> String {
+  channel
+    .itunes_ext()
+    .and_then(|i| i.image())
+    .or(channel.image().map(|i| i.url()))
+    .map(|url| url.trim().to_string())
+    .unwrap_or_default()
+}
+
+// `HH:MM:SS`, `MM:SS` or seconds
+pub fn parse_duration(duration: &str) -> Option<i32> {
+  let mut seconds: f64 = 0.0;
+  for part in duration.trim().split(':') {
+    let value: f64 = part.trim().parse().ok()?;
+    if value < 0.0 {
+      return None;
+    }
+    seconds = seconds * 60.0 + value;
+  }
+
+  Some(seconds as i32)
+}
+
+fn parse_number(number: &str) -> Option<i32> {
+  number.trim().parse().ok()
+}
+
+// `yes`, `true` or `explicit`; `no`, `false` or `clean` otherwise
+fn is_explicit(explicit: &str) -> bool {
+  matches!(
+    explicit.trim().to_lowercase().as_str(),
+    "yes" | "true" | "explicit"
+  )
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_parse_duration() {
+    assert_eq!(parse_duration("1:02:03"), Some(3723));
+    assert_eq!(parse_duration("62:03"), Some(3723));
+    assert_eq!(parse_duration("3723.5"), Some(3723));
+    assert_eq!(parse_duration(""), None);
+  }
+}
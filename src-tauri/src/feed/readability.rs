@@ -0,0 +1,325 @@
+// main content of an article page, for feeds carrying a summary only
+//
+// readability style: paragraphs score their parent and grandparent,
+// per the length and commas of their text; the best scored element,
+// less its link density, is the content, cleaned of the page chrome
+
+use reqwest::Url;
+use scraper::{ElementRef, Html, Node, Selector};
+use std::collections::HashMap;
+
+use super::{client, FeedError};
+
+// dropped with all inside
+const DROP_TAGS: [&str; 17] = [
+  "script", "style", "noscript", "template", "nav", "aside", "header", "footer",
+  "form", "button", "input", "select", "textarea", "iframe", "object", "embed",
+  "svg",
+];
+
+// kept as is, the others are unwrapped
+const KEEP_TAGS: [&str; 40] = [
+  "p",
+  "div",
+  "section",
+  "article",
+  "h1",
+  "h2",
+  "h3",
+  "h4",
+  "h5",
+  "h6",
+  "a",
+  "img",
+  "picture",
+  "source",
+  "figure",
+  "figcaption",
+  "ul",
+  "ol",
+  "li",
+  "dl",
+  "dt",
+  "dd",
+  "blockquote",
+  "pre",
+  "code",
+  "em",
+  "strong",
+  "b",
+  "i",
+  "u",
+  "s",
+  "sub",
+  "sup",
+  "br",
+  "hr",
+  "table",
+  "thead",
+  "tbody",
+  "tr",
+  "td",
+];
+
+const VOID_TAGS: [&str; 4] = ["br", "hr", "img", "source"];
+
+const SAFE_SCHEMES: [&str; 3] = ["http", "https", "mailto"];
+
+// hints in class or id
+const POSITIVE_HINTS: [&str; 8] = [
+  "article", "body", "content", "entry", "main", "post", "story", "text",
+];
+const NEGATIVE_HINTS: [&str; 14] = [
+  "comment",
+  "meta",
+  "footer",
+  "sidebar",
+  "share",
+  "social",
+  "related",
+  "promo",
+  "advert",
+  "banner",
+  "newsletter",
+  "subscribe",
+  "widget",
+  "nav",
+];
+
+// less text is taken as failed
+const MIN_TEXT_LEN: usize = 250;
+
+// download the article page and extract its main content
+pub async fn fetch(url: &str) -> Result<String, FeedError> {
+  let response = client::client()?.get(url).send().await?;
+  if !response.status().is_success() {
+    return Err(FeedError::Status {
+      code: response.status().as_u16(),
+    });
+  }
+  // links are relative to the final url, after redirects
+  let page_url = response.url().to_string();
+  let html = response.text().await?;
+
+  extract(&html, &page_url).ok_or(FeedError::NoContent {
+    url: url.to_string(),
+  })
+}
+
+// the cleaned html of the main content, None if not found
+pub fn extract(html: &str, page_url: &str) -> Option<String> {
+  let document = Html::parse_document(html);
+  let base = Url::parse(page_url).ok();
+  let selector = Selector::parse("p, pre, td").ok()?;
+
+  let mut scores: HashMap<_, f64> = HashMap::new();
+  for paragraph in document.select(&selector) {
+    if in_dropped(paragraph) {
+      continue;
+    }
+    let text: String = paragraph.text().collect();
+    let len = text.trim().chars().count();
+    if len < 25 {
+      continue;
+    }
+
+    let score = 1.0 + text.matches(',').count() as f64 + (len / 100).min(3) as f64;
+    let ancestors = paragraph.ancestors().filter_map(ElementRef::wrap).take(2);
+    for (level, ancestor) in ancestors.enumerate() {
+      *scores
+        .entry(ancestor.id())
+        .or_insert_with(|| initial_score(ancestor)) += score / (level + 1) as f64;
+    }
+  }
+
+  let best = scores
+    .into_iter()
+    .filter_map(|(id, score)| {
+      let element = ElementRef::wrap(document.tree.get(id)?)?;
+      Some((element, score * (1.0 - link_density(element))))
+    })
+    .max_by(|a, b| a.1.total_cmp(&b.1))?
+    .0;
+  if text_len(best) < MIN_TEXT_LEN {
+    return None;
+  }
+
+  let mut content = String::new();
+  write_children(&mut content, best, base.as_ref());
+
+  Some(content.trim().to_string())
+}
+
+fn in_dropped(element: ElementRef) -> bool {
+  std::iter::once(element)
+    .chain(element.ancestors().filter_map(ElementRef::wrap))
+    .any(|el| DROP_TAGS.contains(&el.value().name()))
+}
+
+fn hints(element: ElementRef) -> String {
+  let el = element.value();
+  format!(
+    "{} {}",
+    el.attr("class").unwrap_or(""),
+    el.id().unwrap_or("")
+  )
+  .to_lowercase()
+}
+
+fn initial_score(element: ElementRef) -> f64 {
+  let tag_score = match element.value().name() {
+    "article" | "main" => 10.0,
+    "div" => 5.0,
+    "pre" | "td" | "blockquote" => 3.0,
+    "ol" | "ul" | "dl" | "li" | "form" => -3.0,
+    "h1" | "h2" | "h3" | "h4" | "h5" | "h6" | "th" => -5.0,
+    _ => 0.0,
+  };
+  let hints = hints(element);
+  let positive = POSITIVE_HINTS.iter().any(|h| hints.contains(h));
+  let negative = NEGATIVE_HINTS.iter().any(|h| hints.contains(h));
+
+  tag_score + if positive { 25.0 } else { 0.0 } - if negative { 25.0 } else { 0.0 }
+}
+
+fn text_len(element: ElementRef) -> usize {
+  element.text().map(|t| t.trim().chars().count()).sum()
+}
+
+// share of the text in links
+fn link_density(element: ElementRef) -> f64 {
+  let total = text_len(element);
+  if total == 0 {
+    return 1.0;
+  }
+  let selector = match Selector::parse("a") {
+    Ok(selector) => selector,
+    Err(_) => return 0.0,
+  };
+  let linked: usize = element.select(&selector).map(text_len).sum();
+
+  linked as f64 / total as f64
+}
+
+fn write_children(out: &mut String, element: ElementRef, base: Option<&Url>) {
+  for child in element.children() {
+    match child.value() {
+      Node::Text(text) => out.push_str(&escape(text, false)),
+      Node::Element(_) => {
+        if let Some(child) = ElementRef::wrap(child) {
+          write_element(out, child, base);
+        }
+      }
+      _ => {}
+    }
+  }
+}
+
+fn write_element(out: &mut String, element: ElementRef, base: Option<&Url>) {
+  let name = element.value().name();
+  if DROP_TAGS.contains(&name) {
+    return;
+  }
+  let hints = hints(element);
+  if name != "p" && NEGATIVE_HINTS.iter().any(|h| hints.contains(h)) {
+    return;
+  }
+  if !KEEP_TAGS.contains(&name) {
+    write_children(out, element, base);
+    return;
+  }
+
+  out.push('<');
+  out.push_str(name);
+  for attr in ["href", "src", "alt", "title"] {
+    let value = match element.value().attr(attr) {
+      Some(value) => value.trim(),
+      None => continue,
+    };
+    let value = match attr {
+      "href" | "src" => match safe_url(value, base) {
+        Some(url) => url,
+        None => continue,
+      },
+      _ => value.to_string(),
+    };
+    out.push_str(&format!(" {}=\"{}\"", attr, escape(&value, true)));
+  }
+  out.push('>');
+  if VOID_TAGS.contains(&name) {
+    return;
+  }
+
+  write_children(out, element, base);
+  out.push_str(&format!("</{}>", name));
+}
+
+// the content is rendered as is, so only the web and mail links are kept,
+// no javascript: or data: ones
+fn safe_url(value: &str, base: Option<&Url>) -> Option<String> {
+  let url = base?.join(value).ok()?;
+
+  SAFE_SCHEMES
+    .contains(&url.scheme())
+    .then(|| url.to_string())
+}
+
+fn escape(text: &str, in_attr: bool) -> String {
+  let mut escaped = String::with_capacity(text.len());
+  for c in text.chars() {
+    match c {
+      '&' => escaped.push_str("&amp;"),
+      '<' => escaped.push_str("&lt;"),
+      '>' => escaped.push_str("&gt;"),
+      '"' if in_attr => escaped.push_str("&quot;"),
+      c => escaped.push(c),
+    }
+  }
+
+  escaped
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_readability() {
+    let text = "Feeds often carry a summary only, so the article page is \
+      fetched, and its main content is kept in place of the summary. ";
+    let html = format!(
+      r#"<html><head><script>var a = 1;</script></head><body>
+      <nav class="menu"><a href="/">Home</a><a href="/about">About</a></nav>
+      <div id="main"><article class="post">
+        <h1>Full Article</h1>
+        <p>{text}{text}</p>
+        <p>{text}<a href="/more">More</a> &amp; <span>so on</span></p>
+        <img src="img/a.png" alt="A" onerror="alert(1)">
+        <p><a href=" javascript:alert(1)">Run</a>
+        <img src="data:image/svg+xml,x" alt="B"></p>
+        <div class="share-buttons"><a href="/share">Share</a></div>
+        <script>track();</script>
+      </article>
+      <aside class="sidebar"><p>{text}</p></aside></div>
+      <div class="comments"><p>Nice post, thanks, really, very nice.</p></div>
+      </body></html>"#
+    );
+    let content = extract(&html, "https://mdsilo.com/blog/post.html").unwrap();
+    assert!(content.starts_with("<h1>Full Article</h1>"));
+    assert!(
+      content.contains(r#"<a href="https://mdsilo.com/more">More</a> &amp; so on"#)
+    );
+    assert!(
+      content.contains(r#"<img src="https://mdsilo.com/blog/img/a.png" alt="A">"#)
+    );
+    assert!(content.contains(r#"<a>Run</a>"#));
+    assert!(content.contains(r#"<img alt="B">"#));
+    assert!(!content.contains("javascript"));
+    assert!(!content.contains("data:"));
+    assert!(!content.contains("Home"));
+    assert!(!content.contains("Share"));
+    assert!(!content.contains("script"));
+    assert!(!content.contains("Nice post"));
+    assert_eq!(extract("<p>Too short.</p>", "https://mdsilo.com"), None);
+  }
+}
@@ -0,0 +1,215 @@
+// refresh the channels in background, even with the window hidden
+//
+// a channel is due once its interval passed since the last fetch:
+// the interval of the channel if set, otherwise the global one, but
+// not shorter than the hint of the feed, itself at most a day;
+// doubled per failure in a row
+
+use chrono::{DateTime, Duration, Utc};
+use futures::stream::{self, StreamExt};
+use rss::extension::syndication::UpdatePeriod;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use tauri::{AppHandle, Manager};
+
+use super::{
+  date, get_global_retention, get_unread_num, prune_channel, refresh_channel,
+  DEFAULT_CONCURRENCY,
+};
+use crate::db;
+use crate::models::Channel;
+use crate::storage;
+use crate::tray;
+
+// storage key of the schedule settings
+const SCHEDULE_KEY: &str = "feed_schedule";
+
+// check for the due channels every minute
+const TICK: std::time::Duration = std::time::Duration::from_secs(60);
+const MIN_INTERVAL_MINS: i64 = 5;
+// the backoff of a failing channel stops growing at a day
+const MAX_BACKOFF_MINS: i64 = 24 * 60;
+// a feed hinting a longer period, like `yearly`, is still fetched daily
+const MAX_HINT_MINS: i64 = 24 * 60;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ScheduleSettings {
+  pub enabled: bool,
+  // in minutes
+  pub interval_mins: u32,
+}
+
+impl Default for ScheduleSettings {
+  fn default() -> Self {
+    ScheduleSettings {
+      enabled: true,
+      interval_mins: 60,
+    }
+  }
+}
+
+// emitted to all windows after a background refresh,
+// or all articles marked read from the tray
+#[derive(Debug, Clone, Serialize)]
+pub struct FeedsUpdated {
+  pub refreshed_num: usize,
+  pub new_num: usize,
+  pub unread: HashMap<String, i32>,
+}
+
+pub fn load_settings() -> ScheduleSettings {
+  match storage::get_data(SCHEDULE_KEY.to_string()) {
+    Ok(data) => serde_json::from_value(data.data).unwrap_or_default(),
+    Err(_) => ScheduleSettings::default(),
+  }
+}
+
+pub fn save_settings(settings: &ScheduleSettings) -> bool {
+  match serde_json::to_value(settings) {
+    Ok(value) => storage::set_data(SCHEDULE_KEY.to_string(), value),
+    Err(_) => false,
+  }
+}
+
+// refresh hint of a rss channel in minutes, per <ttl> or sy:updatePeriod
+pub fn read_ttl(channel: &rss::Channel) -> Option<i32> {
+  let ttl = channel
+    .ttl()
+    .and_then(|ttl| ttl.trim().parse::<i32>().ok())
+    .filter(|ttl| *ttl > 0);
+  let period = channel.syndication_ext().map(|sy| {
+    let period_mins = match sy.period() {
+      UpdatePeriod::Hourly => 60,
+      UpdatePeriod::Daily => 24 * 60,
+      UpdatePeriod::Weekly => 7 * 24 * 60,
+      UpdatePeriod::Monthly => 30 * 24 * 60,
+      UpdatePeriod::Yearly => 365 * 24 * 60,
+    };
+    period_mins / sy.frequency().max(1) as i32
+  });
+
+  ttl.or(period)
+}
+
+// minutes between two fetches of the channel
+pub fn refresh_interval(channel: &Channel, settings: &ScheduleSettings) -> i64 {
+  let interval = match channel.refresh_interval.filter(|mins| *mins > 0) {
+    Some(mins) => mins as i64,
+    None => {
+      let hint = (channel.ttl.unwrap_or(0) as i64).min(MAX_HINT_MINS);
+      (settings.interval_mins as i64).max(hint)
+    }
+  }
+  .max(MIN_INTERVAL_MINS);
+
+  let backoff = interval << channel.failure_count.clamp(0, 10);
+  backoff.min(interval.max(MAX_BACKOFF_MINS))
+}
+
+pub fn is_due(
+  channel: &Channel,
+  settings: &ScheduleSettings,
+  now: DateTime<Utc>,
+) -> bool {
+  match channel.last_fetched {
+    Some(fetched) => {
+      now >= fetched + Duration::minutes(refresh_interval(channel, settings))
+    }
+    None => true,
+  }
+}
+
+// start on launch, the due channels are refreshed at once
+pub fn start(app: AppHandle) {
+  tauri::async_runtime::spawn(async move {
+    let mut ticker = tokio::time::interval(TICK);
+    loop {
+      ticker.tick().await;
+      refresh_due(&app).await;
+    }
+  });
+}
+
+async fn refresh_due(app: &AppHandle) {
+  let settings = load_settings();
+  if !settings.enabled {
+    return;
+  }
+
+  let now = date::now();
+  let due: Vec<Channel> = db::get_channels()
+    .into_iter()
+    .filter(|channel| is_due(channel, &settings, now))
+    .collect();
+  if !due.is_empty() {
+    refresh_channels(app, due).await;
+  }
+}
+
+// refresh all channels at once, like from the tray
+pub async fn refresh_all(app: &AppHandle) {
+  refresh_channels(app, db::get_channels()).await;
+}
+
+async fn refresh_channels(app: &AppHandle, channels: Vec<Channel>) {
+  let results: Vec<(String, usize)> = stream::iter(channels)
+    .map(|channel| async move {
+      let link = channel.link.clone();
+      (link, refresh_channel(app, channel).await.unwrap_or(0))
+    })
+    .buffer_unordered(DEFAULT_CONCURRENCY)
+    .collect()
+    .await;
+
+  let retention = get_global_retention();
+  for (link, _) in results.iter() {
+    prune_channel(link.clone(), &retention);
+  }
+
+  let new_num = results.iter().map(|(_, new_num)| new_num).sum();
+  notify_updated(app, results.len(), new_num);
+}
+
+// update the tray and tell the windows the new unread counts
+pub fn notify_updated(app: &AppHandle, refreshed_num: usize, new_num: usize) {
+  tray::update_unread(app);
+  app
+    .emit_all(
+      "feeds-updated",
+      FeedsUpdated {
+        refreshed_num,
+        new_num,
+        unread: get_unread_num(),
+      },
+    )
+    .unwrap_or(());
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_read_ttl() {
+    let rss = |extra: &str| {
+      format!(
+        r#"<rss version="2.0" xmlns:sy="http://purl.org/rss/1.0/modules/syndication/">
+          <channel><title>T</title><link>https://mdsilo.com</link>
+          <description>d</description>{}</channel></rss>"#,
+        extra
+      )
+    };
+    let ttl_of = |extra: &str| {
+      let channel = rss::Channel::read_from(rss(extra).as_bytes()).unwrap();
+      read_ttl(&channel)
+    };
+
+    assert_eq!(ttl_of("<ttl>90</ttl>"), Some(90));
+    assert_eq!(
+      ttl_of("<sy:updatePeriod>daily</sy:updatePeriod><sy:updateFrequency>2</sy:updateFrequency>"),
+      Some(720)
+    );
+    assert_eq!(ttl_of(""), None);
+  }
+}
@@ -0,0 +1,106 @@
+// the secrets of channel credentials, encrypted at rest
+//
+// ChaCha20-Poly1305 per a random key of this install, in a file of the
+// data dir apart from the db: a copy of mdsilo.db alone leaks nothing
+
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use chacha20poly1305::aead::{Aead, AeadCore, KeyInit, OsRng};
+use chacha20poly1305::{ChaCha20Poly1305, Key, Nonce};
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::sync::OnceLock;
+
+use crate::storage::create_mdsilo_dir;
+
+const KEY_FILE: &str = "secret.key";
+// of an encrypted value, the ones stored before are plain
+const PREFIX: &str = "enc1:";
+const NONCE_LEN: usize = 12;
+
+static KEY: OnceLock<Option<Key>> = OnceLock::new();
+
+fn key() -> Option<&'static Key> {
+  KEY.get_or_init(load_key).as_ref()
+}
+
+// created on first use, only readable by the user
+fn load_key() -> Option<Key> {
+  let path = create_mdsilo_dir()?.join(KEY_FILE);
+  if let Ok(bytes) = fs::read(&path) {
+    return (bytes.len() == 32).then(|| *Key::from_slice(&bytes));
+  }
+
+  let key = ChaCha20Poly1305::generate_key(&mut OsRng);
+  let mut options = OpenOptions::new();
+  options.write(true).create_new(true);
+  #[cfg(unix)]
+  {
+    use std::os::unix::fs::OpenOptionsExt;
+    options.mode(0o600);
+  }
+  options.open(&path).ok()?.write_all(&key).ok()?;
+
+  Some(key)
+}
+
+pub fn encrypt(plain: &str) -> Option<String> {
+  if plain.is_empty() {
+    return Some(String::new());
+  }
+  seal(key()?, plain)
+}
+
+pub fn is_encrypted(stored: &str) -> bool {
+  stored.starts_with(PREFIX)
+}
+
+// a plain value stored before is returned as is,
+// None if it can not be decrypted, like the key file lost
+pub fn decrypt(stored: &str) -> Option<String> {
+  if !is_encrypted(stored) {
+    return Some(stored.to_string());
+  }
+  open(key()?, stored)
+}
+
+pub fn seal(key: &Key, plain: &str) -> Option<String> {
+  let nonce = ChaCha20Poly1305::generate_nonce(&mut OsRng);
+  let mut sealed = nonce.to_vec();
+  sealed.extend(
+    ChaCha20Poly1305::new(key)
+      .encrypt(&nonce, plain.as_bytes())
+      .ok()?,
+  );
+
+  Some(format!("{}{}", PREFIX, STANDARD.encode(sealed)))
+}
+
+pub fn open(key: &Key, stored: &str) -> Option<String> {
+  let sealed = STANDARD.decode(stored.strip_prefix(PREFIX)?).ok()?;
+  if sealed.len() < NONCE_LEN {
+    return None;
+  }
+  let (nonce, cipher) = sealed.split_at(NONCE_LEN);
+  let plain = ChaCha20Poly1305::new(key)
+    .decrypt(Nonce::from_slice(nonce), cipher)
+    .ok()?;
+
+  String::from_utf8(plain).ok()
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_seal() {
+    let key = [7u8; 32].into();
+    let sealed = seal(&key, "p@ss").unwrap();
+    assert!(is_encrypted(&sealed) && !sealed.contains("p@ss"));
+    assert_eq!(open(&key, &sealed).as_deref(), Some("p@ss"));
+    assert_eq!(open(&[8u8; 32].into(), &sealed), None);
+    // stored before the encryption
+    assert!(!is_encrypted("p@ss"));
+  }
+}
@@ -42,6 +42,8 @@ fn main() {
   connection
     .run_pending_migrations(MIGRATIONS)
     .expect("Error on migrating");
+  // dates saved verbatim by older versions
+  db::normalize_published();
 
   tauri::Builder::default()
     .plugin(plugins::inject_plugin())
@@ -52,15 +54,65 @@ fn main() {
       feed::fetch_feed,
       feed::add_channel,
       feed::import_channels,
+      feed::import_opml,
+      feed::export_opml,
       feed::get_channels,
       feed::delete_channel,
       feed::add_articles_with_channel,
+      feed::refresh_all_channels,
       feed::get_articles,
+      feed::search_articles,
       feed::get_article_by_url,
       feed::update_article_read_status,
       feed::update_article_star_status,
+      feed::get_article_by_id,
+      feed::update_article_read_status_by_id,
+      feed::update_article_star_status_by_id,
       feed::get_unread_num,
+      feed::get_folder_unread_num,
+      feed::get_folders,
+      feed::create_folder,
+      feed::rename_folder,
+      feed::delete_folder,
+      feed::get_channel_folders,
+      feed::set_channel_folder,
+      feed::get_global_retention,
+      feed::set_global_retention,
+      feed::get_channel_retention,
+      feed::set_channel_retention,
+      feed::prune_articles,
+      feed::save_playback,
+      feed::get_playback,
+      feed::get_playbacks,
+      feed::set_playback_completed,
+      feed::get_play_queue,
+      feed::add_to_queue,
+      feed::remove_from_queue,
+      feed::reorder_queue,
+      feed::clear_queue,
+      feed::get_filter_rules,
+      feed::create_filter_rule,
+      feed::delete_filter_rule,
+      feed::test_filter_rule,
+      feed::apply_filter_rules,
+      feed::get_episode_cache_settings,
+      feed::set_episode_cache_settings,
+      feed::download_episode,
+      feed::delete_episode,
+      feed::get_episode_cache_size,
+      feed::clean_episode_cache,
+      feed::get_http_settings,
+      feed::set_http_settings,
+      feed::get_channel_auth,
+      feed::set_channel_auth,
+      feed::get_broken_channels,
+      feed::get_schedule_settings,
+      feed::set_schedule_settings,
+      feed::set_channel_refresh_interval,
+      feed::set_channel_notify,
       feed::update_all_read_status,
+      feed::fetch_full_article,
+      feed::set_channel_full_article,
       files::read_directory,
       files::is_dir,
       files::is_file,
@@ -96,6 +148,11 @@ fn main() {
       // json::save_notes,
       // json::get_notes,
     ])
+    .setup(|app| {
+      tray::update_unread(&app.handle());
+      feed::schedule::start(app.handle());
+      Ok(())
+    })
     .system_tray(tray::menu())
     .on_system_tray_event(tray::handler)
     .run(tauri::generate_context!())
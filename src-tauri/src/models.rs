@@ -1,4 +1,5 @@
-use super::schema::{articles, channels};
+use super::schema::{articles, channels, filter_rules, play_queue};
+use chrono::{DateTime, Utc};
 use diesel::{sql_types::*, Insertable, Queryable, QueryableByName};
 use serde::{Deserialize, Serialize};
 
@@ -12,10 +13,37 @@ pub struct Channel {
   pub link: String,
   #[diesel(sql_type = Text)]
   pub description: String,
-  #[diesel(sql_type = Text)]
-  pub published: String,
+  #[diesel(sql_type = Nullable<TimestamptzSqlite>)]
+  pub published: Option<DateTime<Utc>>,
   #[diesel(sql_type = Text)]
   pub ty: String, // podcast || rss
+  #[diesel(sql_type = Text)]
+  pub etag: String,
+  #[diesel(sql_type = Text)]
+  pub last_modified: String,
+  #[diesel(sql_type = Text)]
+  pub image: String,
+  #[diesel(sql_type = Nullable<TimestamptzSqlite>)]
+  pub last_fetched: Option<DateTime<Utc>>,
+  #[diesel(sql_type = Nullable<TimestamptzSqlite>)]
+  pub last_success: Option<DateTime<Utc>>,
+  // consecutive failed fetches
+  #[diesel(sql_type = Integer)]
+  pub failure_count: i32,
+  #[diesel(sql_type = Text)]
+  pub last_error: String,
+  // in minutes, hinted by the feed
+  #[diesel(sql_type = Nullable<Integer>)]
+  pub ttl: Option<i32>,
+  // in minutes, the global one on None
+  #[diesel(sql_type = Nullable<Integer>)]
+  pub refresh_interval: Option<i32>,
+  // notify the new articles on refresh
+  #[diesel(sql_type = Bool)]
+  pub notify: bool,
+  // extract the full content of the new articles on refresh
+  #[diesel(sql_type = Bool)]
+  pub full_article: bool,
 }
 
 #[derive(Debug, Queryable, Serialize, QueryableByName)]
@@ -32,8 +60,8 @@ pub struct Article {
   pub audio_url: String,
   #[diesel(sql_type = Text)]
   pub description: String,
-  #[diesel(sql_type = Text)]
-  pub published: String,
+  #[diesel(sql_type = TimestamptzSqlite)]
+  pub published: DateTime<Utc>,
   #[diesel(sql_type = Text)]
   pub content: String,
   #[diesel(sql_type = Text)]
@@ -44,6 +72,109 @@ pub struct Article {
   pub read_status: i32,
   #[diesel(sql_type = Integer)]
   pub star_status: i32,
+  #[diesel(sql_type = Integer)]
+  pub update_status: i32, // 1: revised by the feed
+  #[diesel(sql_type = Text)]
+  pub guid: String,
+  #[diesel(sql_type = Text)]
+  pub categories: String,
+  #[diesel(sql_type = Text)]
+  pub video_url: String,
+  // podcast episode
+  #[diesel(sql_type = Nullable<Integer>)]
+  pub duration: Option<i32>, // seconds
+  #[diesel(sql_type = Nullable<Integer>)]
+  pub season: Option<i32>,
+  #[diesel(sql_type = Nullable<Integer>)]
+  pub episode: Option<i32>,
+  #[diesel(sql_type = Bool)]
+  pub explicit: bool,
+  #[diesel(sql_type = Text)]
+  pub chapters_url: String,
+  #[diesel(sql_type = Text)]
+  pub transcript_url: String,
+  // downloaded audio_url
+  #[diesel(sql_type = Text)]
+  pub local_audio_path: String,
+  // hidden by a filter rule
+  #[diesel(sql_type = Integer)]
+  pub hide_status: i32,
+  // 1: content extracted from the article page
+  #[diesel(sql_type = Integer)]
+  pub full_status: i32,
+}
+
+#[derive(Debug, Queryable, Serialize, QueryableByName)]
+pub struct Folder {
+  #[diesel(sql_type = Integer)]
+  pub id: i32,
+  #[diesel(sql_type = Text)]
+  pub name: String,
+}
+
+// keep the last N articles and those of the last N days,
+// starred articles are always kept
+#[derive(Debug, Default, Clone, Queryable, Serialize, Deserialize)]
+pub struct RetentionPolicy {
+  pub keep_num: Option<i32>,
+  pub keep_days: Option<i32>,
+  // keep unread articles too
+  #[serde(default)]
+  pub keep_unread: bool,
+}
+
+// credentials of a channel, `basic` or `bearer` kind;
+// the secret is never sent back to the webview
+#[derive(Debug, Default, Clone, Queryable, Serialize, Deserialize)]
+pub struct ChannelAuth {
+  pub kind: String,
+  #[serde(default)]
+  pub username: String,
+  #[serde(default, skip_serializing)]
+  pub secret: String,
+}
+
+// on the articles of a channel, or all channels on None:
+// `keyword`, `regex`, `author` or `category` kind matching the pattern,
+// to `read`, `star`, `hide` or `delete`
+#[derive(Debug, Clone, Queryable, Serialize)]
+pub struct FilterRule {
+  pub id: i32,
+  pub channel_link: Option<String>,
+  pub kind: String,
+  pub pattern: String,
+  pub action: String,
+}
+
+#[derive(Debug, Clone, Insertable, Deserialize)]
+#[diesel(table_name = filter_rules)]
+pub struct NewFilterRule {
+  pub channel_link: Option<String>,
+  pub kind: String,
+  pub pattern: String,
+  pub action: String,
+}
+
+#[derive(Debug, Queryable, Serialize)]
+pub struct Playback {
+  pub audio_url: String,
+  pub position: f64, // seconds
+  pub duration: Option<f64>,
+  pub completed: bool,
+  pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Queryable, Insertable, Serialize, Deserialize)]
+#[diesel(table_name = play_queue)]
+pub struct QueueItem {
+  pub audio_url: String,
+  pub title: String,
+  pub article_url: String,
+  pub feed_link: String,
+  pub published: Option<DateTime<Utc>>,
+  // set on queueing
+  #[serde(default)]
+  pub queue_order: i32,
 }
 
 #[derive(Debug, Insertable, Serialize, Deserialize)]
@@ -52,8 +183,12 @@ pub struct NewChannel {
   pub title: String,
   pub link: String,
   pub description: String,
-  pub published: String,
+  pub published: Option<DateTime<Utc>>,
   pub ty: String,
+  pub etag: String,
+  pub last_modified: String,
+  pub image: String,
+  pub ttl: Option<i32>,
 }
 
 #[derive(Debug, Insertable, Clone, Serialize, Deserialize)]
@@ -65,9 +200,21 @@ pub struct NewArticle {
   pub audio_url: String,
   pub description: String,
   pub content: String,
-  pub published: String,
+  pub published: DateTime<Utc>,
   pub author: String,
   pub image: String,
+  // RSS guid, Atom or JSON Feed id, or the url; unique per feed
+  pub guid: String,
+  // joined by `, `
+  pub categories: String,
+  pub video_url: String,
+  // podcast episode
+  pub duration: Option<i32>, // seconds
+  pub season: Option<i32>,
+  pub episode: Option<i32>,
+  pub explicit: bool,
+  pub chapters_url: String,
+  pub transcript_url: String,
 }
 
 // #[derive(Debug, Insertable, Queryable, Serialize, QueryableByName)]
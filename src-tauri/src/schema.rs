@@ -8,12 +8,25 @@ diesel::table! {
     feed_link -> Text,
     audio_url -> Text,
     description -> Text,
-    published -> Timestamp,
+    published -> TimestamptzSqlite,
     content -> Text,
     author -> Text,
     image -> Text,
     read_status -> Integer,
     star_status -> Integer,
+    update_status -> Integer,
+    guid -> Text,
+    categories -> Text,
+    video_url -> Text,
+    duration -> Nullable<Integer>,
+    season -> Nullable<Integer>,
+    episode -> Nullable<Integer>,
+    explicit -> Bool,
+    chapters_url -> Text,
+    transcript_url -> Text,
+    local_audio_path -> Text,
+    hide_status -> Integer,
+    full_status -> Integer,
   }
 }
 
@@ -23,8 +36,89 @@ diesel::table! {
     title -> Text,
     link -> Text,
     description -> Text,
-    published -> Timestamp,
+    published -> Nullable<TimestamptzSqlite>,
     ty -> Text,
+    etag -> Text,
+    last_modified -> Text,
+    image -> Text,
+    last_fetched -> Nullable<TimestamptzSqlite>,
+    last_success -> Nullable<TimestamptzSqlite>,
+    failure_count -> Integer,
+    last_error -> Text,
+    ttl -> Nullable<Integer>,
+    refresh_interval -> Nullable<Integer>,
+    notify -> Bool,
+    full_article -> Bool,
+  }
+}
+
+diesel::table! {
+  folders (id) {
+    id -> Integer,
+    name -> Text,
+  }
+}
+
+diesel::table! {
+  channel_folders (channel_link) {
+    channel_link -> Text,
+    folder_id -> Integer,
+  }
+}
+
+diesel::table! {
+  channel_retention (channel_link) {
+    channel_link -> Text,
+    keep_num -> Nullable<Integer>,
+    keep_days -> Nullable<Integer>,
+    keep_unread -> Bool,
+  }
+}
+
+diesel::table! {
+  channel_auth (channel_link) {
+    channel_link -> Text,
+    kind -> Text,
+    username -> Text,
+    secret -> Text,
+  }
+}
+
+diesel::table! {
+  filter_rules (id) {
+    id -> Integer,
+    channel_link -> Nullable<Text>,
+    kind -> Text,
+    pattern -> Text,
+    action -> Text,
+  }
+}
+
+diesel::table! {
+  pruned_articles (feed_link, guid) {
+    feed_link -> Text,
+    guid -> Text,
+  }
+}
+
+diesel::table! {
+  playback (audio_url) {
+    audio_url -> Text,
+    position -> Double,
+    duration -> Nullable<Double>,
+    completed -> Bool,
+    updated_at -> TimestamptzSqlite,
+  }
+}
+
+diesel::table! {
+  play_queue (audio_url) {
+    audio_url -> Text,
+    title -> Text,
+    article_url -> Text,
+    feed_link -> Text,
+    published -> Nullable<TimestamptzSqlite>,
+    queue_order -> Integer,
   }
 }
 
@@ -36,4 +130,15 @@ diesel::table! {
 //   }
 // }
 
-diesel::allow_tables_to_appear_in_same_query!(articles, channels,);
+diesel::allow_tables_to_appear_in_same_query!(
+  articles,
+  channels,
+  folders,
+  channel_folders,
+  channel_retention,
+  channel_auth,
+  filter_rules,
+  pruned_articles,
+  playback,
+  play_queue,
+);
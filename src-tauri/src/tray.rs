@@ -4,13 +4,26 @@ use tauri::{
   SystemTrayMenuItem,
 };
 
+use crate::db;
+use crate::feed;
+
 pub const MAIN_WIN: &str = "main";
 
 pub fn menu() -> SystemTray {
+  let unread = CustomMenuItem::new("unread".to_string(), unread_label(0)).disabled();
+  let refresh = CustomMenuItem::new("refresh".to_string(), "Refresh all feeds");
+  let mark_read = CustomMenuItem::new("mark_read".to_string(), "Mark all read");
+  let daily_note = CustomMenuItem::new("daily_note".to_string(), "New daily note");
   let show = CustomMenuItem::new("show".to_string(), "Show");
   let hide = CustomMenuItem::new("hide".to_string(), "Hide");
   let quit = CustomMenuItem::new("quit".to_string(), "Quit");
   let tray_menu = SystemTrayMenu::new()
+    .add_item(unread)
+    .add_item(refresh)
+    .add_item(mark_read)
+    .add_native_item(SystemTrayMenuItem::Separator)
+    .add_item(daily_note)
+    .add_native_item(SystemTrayMenuItem::Separator)
     .add_item(show)
     .add_item(hide)
     .add_native_item(SystemTrayMenuItem::Separator)
@@ -56,9 +69,59 @@ pub fn handler(app: &AppHandle, event: SystemTrayEvent) {
           window.hide().unwrap_or(());
         }
       }
+      "refresh" => {
+        let app = app.clone();
+        tauri::async_runtime::spawn(async move {
+          feed::schedule::refresh_all(&app).await;
+        });
+      }
+      "mark_read" => {
+        db::mark_all_read();
+        feed::schedule::notify_updated(app, 0, 0);
+      }
+      // the note is created by the main window
+      "daily_note" => {
+        if let Some(window) = app.get_window(MAIN_WIN) {
+          window.unminimize().unwrap_or(());
+          window.show().unwrap_or(());
+          window.set_focus().unwrap_or(());
+          window.emit("new-daily-note", ()).unwrap_or(());
+        }
+      }
       "quit" => app.exit(0),
-      _ => {} // TODO: MORE EVENT
+      _ => {}
     },
     _ => {}
   }
 }
+
+fn unread_label(unread: i32) -> String {
+  match unread {
+    0 => "No unread articles".to_string(),
+    1 => "1 unread article".to_string(),
+    n => format!("{} unread articles", n),
+  }
+}
+
+// show the total unread count on the tray
+pub fn update_unread(app: &AppHandle) {
+  let unread: i32 = db::get_unread_num().iter().map(|u| u.unread_count).sum();
+  let tray = app.tray_handle();
+  tray
+    .get_item("unread")
+    .set_title(unread_label(unread))
+    .unwrap_or(());
+  tray
+    .set_tooltip(&format!("mdSilo - {}", unread_label(unread)))
+    .unwrap_or(());
+  // next to the icon, only on macOS
+  #[cfg(target_os = "macos")]
+  {
+    let title = if unread > 0 {
+      unread.to_string()
+    } else {
+      String::new()
+    };
+    tray.set_title(&title).unwrap_or(());
+  }
+}
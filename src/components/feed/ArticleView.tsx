@@ -75,7 +75,7 @@ export function ArticleView(props: ViewProps) {
             <span 
               className="m-1 cursor-pointer" 
               onClick={() => setCurrentPod(
-                {title, url: article.audio_url, published: article.published, article_url: article.url, feed_link: article.feed_link}
+                {title, url: article.audio_url, local_path: article.local_audio_path, published: article.published, article_url: article.url, feed_link: article.feed_link}
               )}
             >
               <IconHeadphones size={20} color="purple" />
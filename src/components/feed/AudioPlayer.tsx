@@ -1,4 +1,5 @@
 import { IconPlaylist } from '@tabler/icons-react';
+import { convertFileSrc } from '@tauri-apps/api/tauri';
 import { SidebarTab, store } from 'lib/store';
 import { PodType } from 'types/model';
 
@@ -25,7 +26,12 @@ export default function AudioPlayer(props: Props) {
       <button className='mx-1' onClick={TriggerPlaylist}>
         <IconPlaylist size={24} className="dark:text-slate-300" />
       </button>
-      <audio className="ml-1 h-6" autoPlay controls src={currentPod.url} />
+      <audio 
+        className="ml-1 h-6" 
+        autoPlay 
+        controls 
+        src={currentPod.local_path ? convertFileSrc(currentPod.local_path) : currentPod.url} 
+      />
     </div>
   )
 }
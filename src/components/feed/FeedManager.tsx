@@ -26,6 +26,8 @@ export function FeedManager(props: Props) {
   const [description, setDescription] = useState("");
   const [loading, setLoading] = useState(false);
   const [confirming, setConfirming] = useState(false);
+  // found on a website, choose one to load
+  const [candidates, setCandidates] = useState<dataAgent.FeedCandidate[]>([]);
 
   useEffect(() => {
    setRealList(channelList);
@@ -33,21 +35,33 @@ export function FeedManager(props: Props) {
 
   const handleLoad = async () => {
     setLoading(true);
-    const res = await dataAgent.fetchFeed(feedUrl);
-    // console.log("res from rust", res);
-    if (!res) {
-      setDescription('Cannot find any feed, please check the URL');
+    setCandidates([]);
+    let res;
+    try {
+      res = await dataAgent.fetchFeed(feedUrl);
+    } catch (e) {
+      setDescription(dataAgent.feedErrorMessage(e));
+      setCandidates((e as dataAgent.FeedError)?.candidates || []);
+      setLoading(false);
       return;
     }
+    // console.log("res from rust", res);
     const { channel } = res;
     setFeedTitle(channel.title);
     setDescription(channel.description || '');
     setLoading(false);
   };
 
+  const handleChoose = (candidate: dataAgent.FeedCandidate) => {
+    setFeedUrl(candidate.url);
+    setCandidates([]);
+    setDescription("");
+  };
+
   const handleCancel = () => {
     setLoading(false);
     setConfirming(false);
+    setCandidates([]);
     setFeedTitle("");
     setFeedUrl("");
     setDescription("");
@@ -125,6 +139,20 @@ export function FeedManager(props: Props) {
             </div>
           </div>
           <div className="w-full m-1 dark:text-white">{description}</div>
+          {candidates.length > 0 && (
+            <div className="flex flex-col w-full m-1">
+              {candidates.map((candidate) => (
+                <button
+                  key={candidate.url}
+                  className="p-1 text-sm text-left rounded hover:bg-gray-200 dark:text-white dark:hover:bg-gray-600"
+                  onClick={() => handleChoose(candidate)}
+                >
+                  {candidate.title ? `${candidate.title}: ${candidate.url}` : candidate.url}
+                  <span className="ml-2 text-xs text-gray-500">{candidate.format}</span>
+                </button>
+              ))}
+            </div>
+          )}
           <div className="my-1 flex flex-row items-center justify-center">
             <button className="mr-3 m-btn0" onClick={handleLoad}>{loading ? 'Loading...' : 'Load'}</button>
             <button className="mx-3 m-btn1" onClick={handleCancel}>Cancel</button>
@@ -6,14 +6,78 @@ type FeedResult = {
   articles: ArticleType[];
 };
 
-export const fetchFeed = async (url: string): Promise<FeedResult> => {
-  return await invoke('fetch_feed', { url })
+// a feed found on a website
+export type FeedCandidate = {
+  url: string;
+  title: string;
+  format: string;
+};
+
+// fetch_feed and add_channel reject with a FeedError, tagged by kind
+export type FeedError = {
+  kind: string;
+  message?: string;
+  code?: number;
+  content_type?: string;
+  format?: string;
+  candidates?: FeedCandidate[];
+  auth_kind?: string;
+};
+
+export const feedErrorMessage = (e: unknown): string => {
+  const err = e as FeedError;
+  switch (err?.kind) {
+    case 'Network':
+      return `Network error: ${err.message}`;
+    case 'Status':
+      return `Response status: ${err.code}`;
+    case 'Unauthorized':
+      return `Access denied (${err.code}), please check the credentials`;
+    case 'UnknownFormat':
+      return 'Cannot find any feed, please check the URL';
+    case 'Parse':
+      return `Failed to parse the ${err.format} feed: ${err.message}`;
+    case 'MultipleFeeds':
+      return `Found ${err.candidates?.length} feeds, please choose one below`;
+    case 'UnknownAuth':
+      return `Unknown auth kind: ${err.auth_kind}`;
+    default:
+      return err?.kind ? `Error: ${err.message || err.kind}` : String(e);
+  }
+};
+
+// the secret is never sent back, leave it empty to keep the stored one
+export type ChannelAuth = {
+  kind: 'basic' | 'bearer';
+  username?: string;
+  secret?: string;
+};
+
+export const fetchFeed = async (
+  url: string, auth: ChannelAuth | null = null
+): Promise<FeedResult> => {
+  return await invoke('fetch_feed', { url, auth })
 }
 
 export const addChannel = async (
-  url: string, ty: string, title: string | null
+  url: string, ty: string, title: string | null, auth: ChannelAuth | null = null
+): Promise<number> => {
+  return await invoke('add_channel', { url, ty, title, auth })
+}
+
+// refresh a subscribed channel with its stored credentials
+export const addArticlesWithChannel = async (link: string): Promise<number> => {
+  return await invoke('add_articles_with_channel', { link })
+}
+
+export const getChannelAuth = async (link: string): Promise<ChannelAuth | null> => {
+  return await invoke('get_channel_auth', { link })
+}
+
+export const setChannelAuth = async (
+  link: string, auth: ChannelAuth | null
 ): Promise<number> => {
-  return await invoke('add_channel', { url, ty, title })
+  return await invoke('set_channel_auth', { link, auth })
 }
 
 export const importChannels = async (list: string[]) => {
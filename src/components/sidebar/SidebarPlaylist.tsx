@@ -200,6 +200,7 @@ const computePlaylist = async () => {
     .map(a => { return {
       title: a.title, 
       url: a.audio_url, 
+      local_path: a.local_audio_path, 
       published: a.published, 
       article_url: a.url, 
       feed_link: a.feed_link
@@ -1,7 +1,9 @@
+import { useEffect } from 'react';
 import MsEditor from "mdsmirror";
+import { window as appWindow } from '@tauri-apps/api';
 import ErrorBoundary from 'components/misc/ErrorBoundary';
 import { useCurrentViewContext } from 'context/useCurrentView';
-import Chronicle from './chronicle';
+import Chronicle, { useNewDailyNote } from './chronicle';
 import Journals from './journals';
 import Tasks from './tasks';
 import Kanban from './kanban';
@@ -9,10 +11,22 @@ import Graph from './graph';
 import NotePage from './md';
 import HashTags from "./hashtags";
 import Feed from "./feed";
+import { getStrDate } from 'utils/helper';
+import { isTauri } from 'file/util';
 
 export default function MainView() {
   const currentView = useCurrentViewContext();
   const viewTy = currentView.state.view;
+
+  // "New daily note" of the tray menu
+  const onNewDailyNote = useNewDailyNote();
+  useEffect(() => {
+    if (!isTauri) return;
+    const unlisten = appWindow.getCurrent().listen('new-daily-note', () => {
+      onNewDailyNote(getStrDate((new Date()).toString()));
+    });
+    return () => { unlisten.then(fn => fn()); };
+  }, [onNewDailyNote]);
   // 
   return (
     <>
@@ -25,7 +25,41 @@ export default function Chronicle() {
   const currentView = useCurrentViewContext();
   const dispatch = currentView.dispatch;
 
-  const onNewDailyNote = useCallback(async (date: string) => {
+  const onNewDailyNote = useNewDailyNote();
+
+  const today = getStrDate((new Date()).toString());
+
+  return (
+    <ErrorBoundary>
+      <div className="flex flex-1 flex-col flex-shrink-0 py-6 px-12 w-full mx-auto bg-white dark:bg-black dark:text-gray-200 overlfow-y-auto">
+        <div className="flex justify-center my-6">
+          {initDir ? (
+            <FindOrCreateInput
+              className="w-full bg-white rounded shadow-popover dark:bg-gray-800"
+            />
+          ) : null}
+        </div>
+        <div className="my-1 p-1 rounded text-center">
+          <button onClick={() => dispatch({view: 'journal'})} className="link text-2xl">
+            Journals
+          </button>
+          <button className="link w-full mt-2" onClick={() => onNewDailyNote(today)}>
+            Today : {today}
+          </button>
+        </div>
+        <HeatMapAndList onNewDailyNote={onNewDailyNote} />
+      </div>
+    </ErrorBoundary>
+  );
+}
+
+// open the daily note of the date, created if not yet
+export function useNewDailyNote() {
+  const initDir = useStore((state) => state.initDir);
+  const currentView = useCurrentViewContext();
+  const dispatch = currentView.dispatch;
+
+  return useCallback(async (date: string) => {
     if (!initDir || !regDateStr.test(date)) return;
     const noteId = await joinPaths(initDir, ['daily', `${date}.md`]);
     const note = await openFilePath(noteId, true);
@@ -54,31 +88,6 @@ export default function Chronicle() {
     }
     dispatch({view: 'md', params: { noteId }});
   }, [dispatch, initDir]);
-
-  const today = getStrDate((new Date()).toString());
-
-  return (
-    <ErrorBoundary>
-      <div className="flex flex-1 flex-col flex-shrink-0 py-6 px-12 w-full mx-auto bg-white dark:bg-black dark:text-gray-200 overlfow-y-auto">
-        <div className="flex justify-center my-6">
-          {initDir ? (
-            <FindOrCreateInput
-              className="w-full bg-white rounded shadow-popover dark:bg-gray-800"
-            />
-          ) : null}
-        </div>
-        <div className="my-1 p-1 rounded text-center">
-          <button onClick={() => dispatch({view: 'journal'})} className="link text-2xl">
-            Journals
-          </button>
-          <button className="link w-full mt-2" onClick={() => onNewDailyNote(today)}>
-            Today : {today}
-          </button>
-        </div>
-        <HeatMapAndList onNewDailyNote={onNewDailyNote} />
-      </div>
-    </ErrorBoundary>
-  );
 }
 
 type Props = {
@@ -37,16 +37,20 @@ export default function Feed() {
 
   const [refreshing, setRefreshing] = useState(false);
   const [doneNum, setDoneNum] = useState(0);
-  const refreshChannel = async (link: string, ty: string, title: string) => {
-    const res = await dataAgent.addChannel(link, ty, title);
-    return res;
+  const refreshChannel = async (link: string) => {
+    try {
+      return await dataAgent.addArticlesWithChannel(link);
+    } catch (e) {
+      console.error(link, dataAgent.feedErrorMessage(e));
+      return 0;
+    }
   };
 
   const refreshList = async () => {
     setRefreshing(true);
     setDoneNum(0);
     for (const channel of channelList) {
-      await refreshChannel(channel.link, channel.ty, channel.title);
+      await refreshChannel(channel.link);
       setDoneNum(doneNum + 1);
     }
     setRefreshing(false);
@@ -86,9 +90,13 @@ export default function Feed() {
   };
 
   const handleAddFeed = async (feedUrl: string, ty: string, title: string) => {
-    const res = await dataAgent.addChannel(feedUrl, ty, title)
-    if (res > 0) {
-      getList();
+    try {
+      const res = await dataAgent.addChannel(feedUrl, ty, title);
+      if (res > 0) {
+        getList();
+      }
+    } catch (e) {
+      console.error(feedUrl, dataAgent.feedErrorMessage(e));
     }
   };
 
@@ -105,7 +113,7 @@ export default function Feed() {
     setSyncing(true);
     if (currentChannel) {
       // console.log("refresh current channel: ", currentChannel)
-      await dataAgent.addChannel(currentChannel.link, currentChannel.ty, currentChannel.title);
+      await refreshChannel(currentChannel.link);
       await loadArticleList(currentChannel.link);
     }
     setSyncing(false);
@@ -16,6 +16,7 @@ export interface ArticleType {
   url: string;
   feed_link: string;
   audio_url: string;
+  local_audio_path?: string; // the downloaded episode, if any
   description: string;
   published?: Date;
   read_status: number;
@@ -31,6 +32,7 @@ export interface ArticleType {
 export interface PodType {
   title: string;
   url: string;
+  local_path?: string; // played over the url if downloaded
   published?: Date;
   article_url: string;
   feed_link: string;